@@ -169,8 +169,9 @@ in
       exec = "cargo check --quiet";
     };
 
+    # All features, so the dbus and sway tests run too (dbus-daemon is in packages)
     "test:unit" = {
-      exec = "cargo test --quiet";
+      exec = "cargo test --quiet --all-features";
     };
   };
 
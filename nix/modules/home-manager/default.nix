@@ -167,10 +167,36 @@ let
         ${optionalString (themeFilePath != null) "theme_file_path = \"${themeFilePath}\""}
         ${optionalString (reloadMethod ? signal) "reload_signal = \"${reloadMethod.signal}\""}
         ${optionalString (reloadMethod ? process_name) "process_name = \"${reloadMethod.process_name}\""}
-        ${optionalString (reloadMethod ? command) "reload_command = \"\"\"${reloadMethod.command}\"\"\""}''
+        ${optionalString (reloadMethod ? command) "reload_command = \"\"\"${reloadMethod.command}\"\"\""}
+        ${optionalString (reloadMethod ? timeout) "reload_timeout = ${toString reloadMethod.timeout}"}
+        ${optionalString (cfg.${app}.critical or false) "critical = true"}
+        ${optionalString (reloadMethod ? ipc_commands) "ipc_commands = [${lib.concatMapStringsSep ", " (c: "\"${c}\"") reloadMethod.ipc_commands}]"}
+        ${optionalString (reloadMethod ? ipc_socket) "ipc_socket = \"${reloadMethod.ipc_socket}\""}
+        ${optionalString (reloadMethod ? dbus_bus) "dbus_bus = \"${reloadMethod.dbus_bus}\""}
+        ${optionalString (reloadMethod ? dbus_destination) "dbus_destination = \"${reloadMethod.dbus_destination}\""}
+        ${optionalString (reloadMethod ? dbus_path) "dbus_path = \"${reloadMethod.dbus_path}\""}
+        ${optionalString (reloadMethod ? dbus_interface) "dbus_interface = \"${reloadMethod.dbus_interface}\""}
+        ${optionalString (reloadMethod ? dbus_method) "dbus_method = \"${reloadMethod.dbus_method}\""}
+        ${optionalString (reloadMethod ? dbus_args) "dbus_args = ${builtins.toJSON reloadMethod.dbus_args}"}''
     )
     themedApps;
 
+  # Generate [auto] section for automatic polarity switching
+  autoSection = optionalString cfg.auto.enable ''
+    [auto]
+    interval = ${toString cfg.auto.interval}
+    ${optionalString (cfg.auto.light != null) "light = \"${cfg.auto.light}\""}
+    ${optionalString (cfg.auto.dark != null) "dark = \"${cfg.auto.dark}\""}
+    ${optionalString (cfg.auto.latitude != null) "latitude = ${toString cfg.auto.latitude}"}
+    ${optionalString (cfg.auto.longitude != null) "longitude = ${toString cfg.auto.longitude}"}'';
+
+  # Generate [hooks] section for commands run around theme switches
+  hooksSection = optionalString (cfg.hooks.preApply != [ ] || cfg.hooks.postApply != [ ]) ''
+    [hooks]
+    pre_apply = ${builtins.toJSON cfg.hooks.preApply}
+    post_apply = ${builtins.toJSON cfg.hooks.postApply}
+    timeout = ${toString cfg.hooks.timeout}'';
+
   # Generate full config.toml content
   configToml = ''
     # Vogix Theme Configuration
@@ -191,6 +217,11 @@ let
     base16 = "${schemeSources.base16}"
     base24 = "${schemeSources.base24}"
     ansi16 = "${schemeSources.ansi16}"
+    fill_missing = ${lib.boolToString cfg.fillMissingColors}
+
+    ${autoSection}
+
+    ${hooksSection}
 
     ${themesSection}
 
@@ -317,7 +348,28 @@ in
       '';
     })
 
-    # Optional daemon service for auto-regeneration
+    # Switch between light and dark variants on the configured schedule
+    (mkIf cfg.auto.enable {
+      systemd.user.services.vogix-auto = {
+        Unit = {
+          Description = "Vogix automatic light/dark switching";
+          After = [ "graphical-session.target" ];
+        };
+
+        Service = {
+          Type = "simple";
+          ExecStart = "${cfg.package}/bin/vogix auto --quiet";
+          Restart = "on-failure";
+          RestartSec = 5;
+        };
+
+        Install = {
+          WantedBy = [ "default.target" ];
+        };
+      };
+    })
+
+    # Optional daemon serving theme changes over a Unix socket
     (mkIf cfg.enableDaemon {
       systemd.user.services.vogix-daemon = {
         Unit = {
@@ -327,7 +379,7 @@ in
 
         Service = {
           Type = "simple";
-          ExecStart = "${cfg.package}/bin/vogix daemon";
+          ExecStart = "${cfg.package}/bin/vogix daemon --quiet";
           Restart = "on-failure";
           RestartSec = 5;
         };
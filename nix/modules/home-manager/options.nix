@@ -41,6 +41,12 @@ let
             default = null;
             description = "Variant to use for ${appName} (overrides global variant)";
           };
+
+          critical = mkOption {
+            type = types.bool;
+            default = false;
+            description = "Roll a theme switch back if ${appName} fails to reload";
+          };
         };
       })
       availableApps
@@ -88,10 +94,76 @@ in
       description = "Custom theme definitions.";
     };
 
+    auto = {
+      enable = mkEnableOption "automatic light/dark variant switching (vogix auto)";
+
+      light = mkOption {
+        type = types.nullOr types.str;
+        default = null;
+        example = "07:00";
+        description = "Local time (HH:MM) light variants start. Used with `dark` when no location is set.";
+      };
+
+      dark = mkOption {
+        type = types.nullOr types.str;
+        default = null;
+        example = "19:30";
+        description = "Local time (HH:MM) dark variants start.";
+      };
+
+      latitude = mkOption {
+        type = types.nullOr types.float;
+        default = null;
+        example = 52.37;
+        description = "Latitude for sunrise/sunset switching. Takes precedence over fixed times.";
+      };
+
+      longitude = mkOption {
+        type = types.nullOr types.float;
+        default = null;
+        example = 4.9;
+        description = "Longitude for sunrise/sunset switching (east positive).";
+      };
+
+      interval = mkOption {
+        type = types.ints.positive;
+        default = 60;
+        description = "Seconds between schedule checks.";
+      };
+    };
+
+    hooks = {
+      preApply = mkOption {
+        type = types.listOf types.str;
+        default = [ ];
+        example = [ "test -f ~/wallpapers/$VOGIX_THEME-$VOGIX_VARIANT.png" ];
+        description = "Shell commands run before a theme switch. A failing command aborts the switch.";
+      };
+
+      postApply = mkOption {
+        type = types.listOf types.str;
+        default = [ ];
+        example = [ "swww img ~/wallpapers/$VOGIX_THEME-$VOGIX_VARIANT.png" ];
+        description = "Shell commands run after a theme switch. Failures are only logged.";
+      };
+
+      timeout = mkOption {
+        type = types.ints.positive;
+        default = 30;
+        description = "Seconds each hook may run before it is killed.";
+      };
+    };
+
+    fillMissingColors = mkOption {
+      type = types.bool;
+      default = false;
+      description = "Fill colors missing from theme source files with their scheme's fallbacks (e.g. base24 base10-base17 from base16 colors) instead of failing to render the theme.";
+    };
+
     enableDaemon = mkOption {
       type = types.bool;
       default = false;
-      description = "Enable the vogix daemon for auto-regeneration.";
+      description = "Run `vogix daemon`, which serves theme changes over $XDG_RUNTIME_DIR/vogix.sock.";
     };
 
     colors = mkOption {
@@ -18,6 +18,9 @@ rustPlatform.buildRustPackage rec {
     lockFile = ../../Cargo.lock;
   };
 
+  # Reload methods behind cargo features: reloadMethod.method = "dbus" and "sway-ipc"
+  buildFeatures = [ "dbus" "sway" ];
+
   nativeBuildInputs = [
     pkg-config
   ];
@@ -0,0 +1,138 @@
+//! Source hashes and completion markers for rendered variants
+//!
+//! Each rendered variant directory holds a `.source-hash` file with a SHA-256
+//! over everything it was rendered from: the templates hash, whether missing
+//! colors were filled, the template files the scheme renders and the theme source file.
+//! It is written after every template rendered, so it also marks the
+//! directory as complete. A variant without it is incomplete, and one whose
+//! stored hash no longer matches is stale; both get re-rendered.
+
+use crate::errors::{Result, VogixError};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Completion marker inside a rendered variant directory, holding its source hash
+pub const SOURCE_HASH_FILE: &str = ".source-hash";
+
+/// Hash the inputs of a rendered variant
+///
+/// `templates` are hashed in the order given, each with its file name, so
+/// renaming a template changes the hash too.
+pub fn source_hash(
+    templates_hash: &str,
+    fill_missing: bool,
+    templates: &[PathBuf],
+    variant_path: &Path,
+) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hash_field(&mut hasher, templates_hash.as_bytes());
+    hash_field(&mut hasher, &[u8::from(fill_missing)]);
+
+    for template in templates {
+        let name = template.file_name().unwrap_or_default();
+        hash_field(&mut hasher, name.as_encoded_bytes());
+        hash_field(&mut hasher, &fs::read(template)?);
+    }
+
+    let source = fs::read(variant_path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => VogixError::ThemeNotFound(format!(
+            "theme source not found: {}",
+            variant_path.display()
+        )),
+        _ => e.into(),
+    })?;
+    hash_field(&mut hasher, &source);
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Feed one length-prefixed field, so adjacent fields can't run together
+fn hash_field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// The source hash stored in a rendered variant directory, or None if it is incomplete
+pub fn read_stored(cache_path: &Path) -> Option<String> {
+    fs::read_to_string(cache_path.join(SOURCE_HASH_FILE))
+        .ok()
+        .map(|hash| hash.trim().to_string())
+}
+
+/// Store the source hash, marking a rendered variant directory complete
+pub fn write_stored(cache_path: &Path, hash: &str) -> Result<()> {
+    fs::write(cache_path.join(SOURCE_HASH_FILE), format!("{}\n", hash))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(temp_dir: &TempDir) -> (PathBuf, PathBuf) {
+        let template = temp_dir.path().join("a.conf.vogix");
+        fs::write(&template, "{{ colors.base00 }}").unwrap();
+        let source = temp_dir.path().join("dark.toml");
+        fs::write(&source, "base00 = \"#000000\"").unwrap();
+        (template, source)
+    }
+
+    #[test]
+    fn test_source_hash_is_stable() {
+        let temp_dir = TempDir::new().unwrap();
+        let (template, source) = setup(&temp_dir);
+        let templates = [template];
+
+        let first = source_hash("abc", false, &templates, &source).unwrap();
+        assert_eq!(first.len(), 64);
+        assert_eq!(
+            first,
+            source_hash("abc", false, &templates, &source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_source_hash_changes_with_each_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let (template, source) = setup(&temp_dir);
+        let templates = [template];
+        let original = source_hash("abc", false, &templates, &source).unwrap();
+
+        assert_ne!(
+            original,
+            source_hash("def", false, &templates, &source).unwrap()
+        );
+        assert_ne!(
+            original,
+            source_hash("abc", true, &templates, &source).unwrap()
+        );
+        assert_ne!(original, source_hash("abc", false, &[], &source).unwrap());
+
+        let renamed_templates = [temp_dir.path().join("b.conf.vogix")];
+        fs::rename(&templates[0], &renamed_templates[0]).unwrap();
+        let renamed = source_hash("abc", false, &renamed_templates, &source).unwrap();
+        assert_ne!(original, renamed);
+
+        fs::write(&source, "base00 = \"#101010\"").unwrap();
+        assert_ne!(
+            renamed,
+            source_hash("abc", false, &renamed_templates, &source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stored_hash_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(read_stored(temp_dir.path()), None);
+
+        write_stored(temp_dir.path(), "abc123").unwrap();
+        assert_eq!(read_stored(temp_dir.path()).as_deref(), Some("abc123"));
+    }
+}
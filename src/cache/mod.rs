@@ -6,21 +6,70 @@
 //!   ├── btop.conf
 //!   └── ...
 //!
+//! A variant is rendered from the scheme's templates, plus any template in
+//! `common/` the scheme has none of the same name for.
+//!
+//! Variants are rendered into a staging directory and moved into place once
+//! complete. Each variant directory holds a `.source-hash` of the templates
+//! and theme source it was rendered from, which doubles as the completion
+//! marker, so edited sources and interrupted renders are re-rendered.
+//!
 //! # Module Structure
-//! - `paths`: Path resolution (XDG, cache paths, theme sources)
+//! - `hash`: Source hashes for detecting stale variants
+//! - `paths`: Path resolution (XDG, cache paths, theme sources, templates)
 //! - `renderer`: Template rendering to cache
 
+mod hash;
 mod paths;
 mod renderer;
 #[cfg(test)]
 mod tests;
 
+pub use paths::{COMMON_TEMPLATES_DIR, theme_variant_path};
+
 use crate::config::{Config, TemplatesConfig, ThemeSourcesConfig};
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
 use log::debug;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// How a rendered variant compares to its current sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    /// Rendered from the current templates and theme source
+    Fresh,
+    /// The templates or theme source changed since it was rendered
+    Stale,
+    /// No completion marker: an interrupted render, or one by an older vogix
+    Incomplete,
+    /// The theme source file no longer exists
+    SourceMissing,
+    /// Not rendered yet
+    Missing,
+}
+
+impl fmt::Display for CacheStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Fresh => "fresh",
+            Self::Stale => "stale",
+            Self::Incomplete => "incomplete",
+            Self::SourceMissing => "source-missing",
+            Self::Missing => "missing",
+        })
+    }
+}
+
+/// A rendered variant found by [`ThemeCache::verify`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedVariant {
+    pub scheme: Scheme,
+    pub theme: String,
+    pub variant: String,
+    pub status: CacheStatus,
+}
 
 /// Manages the theme configuration cache
 pub struct ThemeCache {
@@ -57,6 +106,11 @@ impl ThemeCache {
         })
     }
 
+    /// Hash of the templates the cache renders with
+    pub fn templates_hash(&self) -> &str {
+        &self.templates.hash
+    }
+
     /// Get the cache path for a specific theme variant
     /// Returns: ~/.cache/vogix/themes/{templates-hash}/{scheme}/{theme}/{variant}/
     pub fn variant_cache_path(&self, scheme: &Scheme, theme: &str, variant: &str) -> PathBuf {
@@ -69,10 +123,38 @@ impl ThemeCache {
         )
     }
 
-    /// Check if a theme variant is cached
-    pub fn is_cached(&self, scheme: &Scheme, theme: &str, variant: &str) -> bool {
+    /// Hash of the templates and theme source a variant is rendered from
+    pub fn source_hash(&self, scheme: &Scheme, theme: &str, variant: &str) -> Result<String> {
+        hash::source_hash(
+            &self.templates.hash,
+            self.theme_sources.fill_missing,
+            &paths::template_files(&self.templates.path, scheme)?,
+            &paths::theme_variant_path(&self.theme_sources, scheme, theme, variant),
+        )
+    }
+
+    /// Compare a variant's rendered configs against its current sources
+    pub fn status(&self, scheme: &Scheme, theme: &str, variant: &str) -> CacheStatus {
         let cache_path = self.variant_cache_path(scheme, theme, variant);
-        cache_path.exists() && cache_path.is_dir()
+        if !cache_path.is_dir() {
+            return CacheStatus::Missing;
+        }
+        let Some(stored) = hash::read_stored(&cache_path) else {
+            return CacheStatus::Incomplete;
+        };
+        if !paths::theme_variant_path(&self.theme_sources, scheme, theme, variant).is_file() {
+            return CacheStatus::SourceMissing;
+        }
+        match self.source_hash(scheme, theme, variant) {
+            Ok(current) if current == stored => CacheStatus::Fresh,
+            _ => CacheStatus::Stale,
+        }
+    }
+
+    /// Check if a theme variant is cached and rendered from its current sources
+    #[cfg(test)]
+    pub fn is_cached(&self, scheme: &Scheme, theme: &str, variant: &str) -> bool {
+        self.status(scheme, theme, variant) == CacheStatus::Fresh
     }
 
     /// Render and cache a theme variant
@@ -81,12 +163,20 @@ impl ThemeCache {
         let cache_path = self.variant_cache_path(scheme, theme, variant);
 
         // Check if already cached
-        if self.is_cached(scheme, theme, variant) {
-            debug!("Using cached configs for {}/{}/{}", scheme, theme, variant);
-            return Ok(cache_path);
+        match self.status(scheme, theme, variant) {
+            CacheStatus::Fresh => {
+                debug!("Using cached configs for {}/{}/{}", scheme, theme, variant);
+                return Ok(cache_path);
+            }
+            CacheStatus::Missing => {}
+            status => debug!(
+                "Re-rendering {}/{}/{}: cache is {}",
+                scheme, theme, variant, status
+            ),
         }
 
-        // Render to cache
+        // Hashed before rendering, so a source edited mid-render is caught next time
+        let source_hash = self.source_hash(scheme, theme, variant)?;
         renderer::render_to_cache(
             &cache_path,
             &self.templates.path,
@@ -94,6 +184,7 @@ impl ThemeCache {
             scheme,
             theme,
             variant,
+            &source_hash,
         )?;
 
         Ok(cache_path)
@@ -104,6 +195,41 @@ impl ThemeCache {
         self.render_variant(scheme, theme, variant)
     }
 
+    /// Check every rendered variant under the current templates hash against its sources
+    ///
+    /// Called by `vogix cache verify` command.
+    pub fn verify(&self) -> Result<Vec<CachedVariant>> {
+        let mut variants = Vec::new();
+        let hash_dir = self.cache_dir.join(&self.templates.hash);
+        for scheme_dir in subdirectories(&hash_dir)? {
+            let Ok(scheme) = dir_name(&scheme_dir).parse::<Scheme>() else {
+                continue;
+            };
+            for theme_dir in subdirectories(&scheme_dir)? {
+                for variant_dir in subdirectories(&theme_dir)? {
+                    let theme = dir_name(&theme_dir);
+                    let variant = dir_name(&variant_dir);
+                    let status = self.status(&scheme, &theme, &variant);
+                    variants.push(CachedVariant {
+                        scheme,
+                        theme,
+                        variant,
+                        status,
+                    });
+                }
+            }
+        }
+
+        variants.sort_by(|a, b| {
+            (a.scheme.to_string(), &a.theme, &a.variant).cmp(&(
+                b.scheme.to_string(),
+                &b.theme,
+                &b.variant,
+            ))
+        });
+        Ok(variants)
+    }
+
     /// Clean old cache entries (keep only current templates hash)
     ///
     /// Removes cache directories for old template hashes to free disk space.
@@ -135,3 +261,24 @@ impl ThemeCache {
         Ok(removed)
     }
 }
+
+/// Directories in `dir`, skipping hidden ones like staging directories
+fn subdirectories(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() && !dir_name(&path).starts_with('.') {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
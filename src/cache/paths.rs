@@ -4,12 +4,18 @@
 //! - XDG base directory resolution
 //! - Cache path construction
 //! - Theme source path resolution
+//! - Template lookup, with the common template fallback
 
 use crate::config::ThemeSourcesConfig;
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
+use std::collections::BTreeMap;
+use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Template directory for every scheme, used where a scheme has no template of its own
+pub const COMMON_TEMPLATES_DIR: &str = "common";
+
 /// Get the base cache directory (~/.cache/vogix)
 ///
 /// Follows XDG Base Directory specification:
@@ -41,6 +47,17 @@ pub fn variant_cache_path(
         .join(variant)
 }
 
+/// Directory a variant is rendered into before it is moved to `cache_path`
+///
+/// Returns: {cache_path parent}/.{variant}.staging-{pid}/
+pub fn staging_path(cache_path: &Path) -> PathBuf {
+    let variant = cache_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    cache_path.with_file_name(format!(".{}.staging-{}", variant, std::process::id()))
+}
+
 /// Get theme source path for a scheme
 pub fn theme_source_path<'a>(
     theme_sources: &'a ThemeSourcesConfig,
@@ -74,6 +91,39 @@ pub fn theme_variant_path(
         .join(format!("{}.{}", variant, extension))
 }
 
+/// Template files a scheme renders, in file name order
+///
+/// Every `.vogix` file in `{templates_path}/{scheme}/`, plus each one in
+/// `{templates_path}/common/` the scheme directory has no file of the same
+/// name for. Either directory may be missing, but not both.
+pub fn template_files(templates_path: &Path, scheme: &Scheme) -> Result<Vec<PathBuf>> {
+    let scheme_dir = templates_path.join(scheme.to_string());
+    let common_dir = templates_path.join(COMMON_TEMPLATES_DIR);
+    if !scheme_dir.is_dir() && !common_dir.is_dir() {
+        return Err(VogixError::Config(format!(
+            "templates directory not found: {}",
+            scheme_dir.display()
+        )));
+    }
+
+    // The scheme directory comes last, so its templates replace common ones
+    let mut templates = BTreeMap::new();
+    for dir in [common_dir, scheme_dir] {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "vogix")
+                && let Some(name) = path.file_name()
+            {
+                templates.insert(name.to_os_string(), path);
+            }
+        }
+    }
+    Ok(templates.into_values().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +147,7 @@ mod tests {
             base16: PathBuf::from("/themes/base16"),
             base24: PathBuf::from("/themes/base24"),
             ansi16: PathBuf::from("/themes/ansi16"),
+            fill_missing: false,
         };
 
         assert_eq!(
@@ -112,6 +163,7 @@ mod tests {
             base16: PathBuf::from("/themes/base16"),
             base24: PathBuf::from("/themes/base24"),
             ansi16: PathBuf::from("/themes/ansi16"),
+            fill_missing: false,
         };
 
         let path = theme_variant_path(&sources, &Scheme::Vogix16, "aikido", "night");
@@ -125,9 +177,45 @@ mod tests {
             base16: PathBuf::from("/themes/base16"),
             base24: PathBuf::from("/themes/base24"),
             ansi16: PathBuf::from("/themes/ansi16"),
+            fill_missing: false,
         };
 
         let path = theme_variant_path(&sources, &Scheme::Base16, "dracula", "default");
         assert_eq!(path, PathBuf::from("/themes/base16/dracula/default.yaml"));
     }
+
+    #[test]
+    fn test_template_files_fall_back_to_common() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let templates = temp_dir.path();
+        fs::create_dir_all(templates.join("base16")).unwrap();
+        fs::create_dir_all(templates.join("common")).unwrap();
+        fs::write(templates.join("base16/btop.theme.vogix"), "").unwrap();
+        fs::write(templates.join("common/btop.theme.vogix"), "").unwrap();
+        fs::write(templates.join("common/alacritty.toml.vogix"), "").unwrap();
+        fs::write(templates.join("common/README"), "").unwrap();
+
+        assert_eq!(
+            template_files(templates, &Scheme::Base16).unwrap(),
+            vec![
+                templates.join("common/alacritty.toml.vogix"),
+                templates.join("base16/btop.theme.vogix"),
+            ]
+        );
+        // No scheme directory at all: only common templates
+        assert_eq!(
+            template_files(templates, &Scheme::Ansi16).unwrap(),
+            vec![
+                templates.join("common/alacritty.toml.vogix"),
+                templates.join("common/btop.theme.vogix"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_files_without_any_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let err = template_files(temp_dir.path(), &Scheme::Base16).unwrap_err();
+        assert!(err.to_string().contains("templates directory not found"));
+    }
 }
@@ -2,29 +2,35 @@
 //!
 //! Handles rendering theme templates to the cache directory.
 
+use crate::color::Color;
 use crate::config::ThemeSourcesConfig;
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
 use crate::template;
 use crate::theme;
 use log::{debug, info};
+use std::ffi::CString;
 use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
 use std::path::Path;
 
-use super::paths;
+use super::{hash, paths};
 
 /// Render all templates for a theme variant to the cache directory
 ///
+/// Templates are rendered into a sibling staging directory, which gets the
+/// completion marker last and only then replaces `cache_path`. A failed
+/// render leaves `cache_path` as it was.
+///
 /// # Arguments
 /// * `cache_path` - Target directory for rendered configs
-/// * `templates_path` - Base path containing scheme template directories
+/// * `templates_path` - Base path containing scheme and common template directories
 /// * `theme_sources` - Theme source configuration
 /// * `scheme` - Color scheme to use
 /// * `theme` - Theme name
 /// * `variant` - Variant name
-///
-/// # Returns
-/// The cache path where configs were written
+/// * `source_hash` - Hash of the sources, stored as the completion marker
 pub fn render_to_cache(
     cache_path: &Path,
     templates_path: &Path,
@@ -32,46 +38,97 @@ pub fn render_to_cache(
     scheme: &Scheme,
     theme: &str,
     variant: &str,
+    source_hash: &str,
 ) -> Result<()> {
     info!(
         "Rendering configs for {}/{}/{} to cache",
         scheme, theme, variant
     );
 
-    // Create cache directory
-    fs::create_dir_all(cache_path)?;
-
-    // Load theme colors from variant file
-    let variant_path = paths::theme_variant_path(theme_sources, scheme, theme, variant);
-    let colors = theme::load_theme_colors(&variant_path, *scheme)?;
-
-    // Find templates for this scheme
-    let scheme_templates_path = templates_path.join(scheme.to_string());
-    if !scheme_templates_path.exists() {
-        return Err(VogixError::Config(format!(
-            "templates directory not found: {}",
-            scheme_templates_path.display()
-        )));
+    let staging_path = paths::staging_path(cache_path);
+    if staging_path.exists() {
+        fs::remove_dir_all(&staging_path)?;
     }
 
-    // Render all .vogix template files
-    for entry in fs::read_dir(&scheme_templates_path)? {
-        let entry = entry?;
-        let template_path = entry.path();
+    let rendered = render_templates(
+        &staging_path,
+        templates_path,
+        theme_sources,
+        scheme,
+        theme,
+        variant,
+    )
+    .and_then(|()| hash::write_stored(&staging_path, source_hash));
+    if let Err(e) = rendered {
+        let _ = fs::remove_dir_all(&staging_path);
+        return Err(e);
+    }
 
-        if template_path.extension().is_some_and(|ext| ext == "vogix") {
-            render_template_file(&template_path, cache_path, &colors)?;
+    if let Err(e) = install(&staging_path, cache_path) {
+        let _ = fs::remove_dir_all(&staging_path);
+        // Another process finished rendering the same variant first
+        if hash::read_stored(cache_path).as_deref() != Some(source_hash) {
+            return Err(e.into());
         }
     }
+    Ok(())
+}
+
+/// Render every template of the scheme, and common ones it doesn't override, into `output_path`
+fn render_templates(
+    output_path: &Path,
+    templates_path: &Path,
+    theme_sources: &ThemeSourcesConfig,
+    scheme: &Scheme,
+    theme: &str,
+    variant: &str,
+) -> Result<()> {
+    fs::create_dir_all(output_path)?;
+
+    // Load theme colors from variant file, with the common roles for shared templates
+    let variant_path = paths::theme_variant_path(theme_sources, scheme, theme, variant);
+    let colors = theme::load_palette(&variant_path, *scheme, theme_sources.fill_missing)?;
+
+    for template_path in paths::template_files(templates_path, scheme)? {
+        render_template_file(&template_path, output_path, &colors)?;
+    }
 
     Ok(())
 }
 
+/// Move a rendered staging directory to `cache_path`
+///
+/// An existing `cache_path` is swapped out atomically with renameat2(2), so a
+/// `current-theme` symlink pointing at it never dangles.
+fn install(staging_path: &Path, cache_path: &Path) -> io::Result<()> {
+    if !cache_path.is_dir() {
+        return fs::rename(staging_path, cache_path);
+    }
+
+    let staging = CString::new(staging_path.as_os_str().as_bytes())?;
+    let cache = CString::new(cache_path.as_os_str().as_bytes())?;
+    // SAFETY: both are valid NUL-terminated strings for the duration of the call.
+    let exchanged = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            staging.as_ptr(),
+            libc::AT_FDCWD,
+            cache.as_ptr(),
+            libc::RENAME_EXCHANGE,
+        )
+    };
+    if exchanged != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // The staging path now holds the replaced variant
+    fs::remove_dir_all(staging_path)
+}
+
 /// Render a single template file to the cache directory
 fn render_template_file(
     template_path: &Path,
     cache_path: &Path,
-    colors: &std::collections::HashMap<String, String>,
+    colors: &std::collections::HashMap<String, Color>,
 ) -> Result<()> {
     // Get output filename (remove .vogix extension)
     let output_name = template_path
@@ -133,6 +190,7 @@ mod tests {
             base16: themes_path.clone(),
             base24: themes_path.clone(),
             ansi16: themes_path,
+            fill_missing: false,
         };
 
         (cache_path, templates_path, theme_sources)
@@ -150,6 +208,7 @@ mod tests {
             &Scheme::Vogix16,
             "test-theme",
             "dark",
+            "hash",
         )
         .unwrap();
 
@@ -168,6 +227,7 @@ mod tests {
             &Scheme::Vogix16,
             "test-theme",
             "dark",
+            "hash",
         )
         .unwrap();
 
@@ -185,6 +245,7 @@ mod tests {
             base16: temp_dir.path().to_path_buf(),
             base24: temp_dir.path().to_path_buf(),
             ansi16: temp_dir.path().to_path_buf(),
+            fill_missing: false,
         };
 
         let result = render_to_cache(
@@ -194,8 +255,117 @@ mod tests {
             &Scheme::Vogix16,
             "test",
             "dark",
+            "hash",
         );
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_render_to_cache_writes_completion_marker() {
+        let temp_dir = TempDir::new().unwrap();
+        let (cache_path, templates_path, theme_sources) = setup_test_env(&temp_dir);
+
+        render_to_cache(
+            &cache_path,
+            &templates_path,
+            &theme_sources,
+            &Scheme::Vogix16,
+            "test-theme",
+            "dark",
+            "abc123",
+        )
+        .unwrap();
+
+        assert_eq!(hash::read_stored(&cache_path).as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn test_render_to_cache_failure_keeps_previous_render() {
+        let temp_dir = TempDir::new().unwrap();
+        let (cache_path, templates_path, theme_sources) = setup_test_env(&temp_dir);
+        render_to_cache(
+            &cache_path,
+            &templates_path,
+            &theme_sources,
+            &Scheme::Vogix16,
+            "test-theme",
+            "dark",
+            "first",
+        )
+        .unwrap();
+
+        fs::write(
+            templates_path.join("vogix16").join("broken.conf.vogix"),
+            "{{ colors.background",
+        )
+        .unwrap();
+        let result = render_to_cache(
+            &cache_path,
+            &templates_path,
+            &theme_sources,
+            &Scheme::Vogix16,
+            "test-theme",
+            "dark",
+            "second",
+        );
+
+        assert!(result.is_err());
+        assert_eq!(hash::read_stored(&cache_path).as_deref(), Some("first"));
+        assert!(!cache_path.join("broken.conf").exists());
+        // Only the cache directory is left, no staging directory
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_render_to_cache_replaces_previous_render() {
+        let temp_dir = TempDir::new().unwrap();
+        let (cache_path, templates_path, theme_sources) = setup_test_env(&temp_dir);
+        fs::write(cache_path.join("leftover.conf"), "old").unwrap();
+
+        render_to_cache(
+            &cache_path,
+            &templates_path,
+            &theme_sources,
+            &Scheme::Vogix16,
+            "test-theme",
+            "dark",
+            "hash",
+        )
+        .unwrap();
+
+        assert!(cache_path.join("test.toml").exists());
+        assert!(!cache_path.join("leftover.conf").exists());
+    }
+
+    #[test]
+    fn test_render_to_cache_falls_back_to_common_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let (cache_path, templates_path, theme_sources) = setup_test_env(&temp_dir);
+        let common_path = templates_path.join("common");
+        fs::create_dir_all(&common_path).unwrap();
+        fs::write(
+            common_path.join("shared.conf.vogix"),
+            "{{ colors.foreground }} {{ colors.danger }} {{ colors.color02 }}",
+        )
+        .unwrap();
+        fs::write(common_path.join("test.toml.vogix"), "overridden").unwrap();
+
+        render_to_cache(
+            &cache_path,
+            &templates_path,
+            &theme_sources,
+            &Scheme::Vogix16,
+            "test-theme",
+            "dark",
+            "hash",
+        )
+        .unwrap();
+
+        let shared = fs::read_to_string(cache_path.join("shared.conf")).unwrap();
+        assert_eq!(shared, "#555555 #00ff00 #ff0000");
+        // The scheme's own template wins over the common one
+        let own = fs::read_to_string(cache_path.join("test.toml")).unwrap();
+        assert_eq!(own, "background = \"#000000\"");
+    }
 }
@@ -50,7 +50,10 @@ fn create_test_config(temp_dir: &TempDir) -> Config {
             base16: themes_path.clone(),
             base24: themes_path.clone(),
             ansi16: themes_path,
+            fill_missing: false,
         }),
+        auto: None,
+        hooks: None,
     }
 }
 
@@ -116,7 +119,10 @@ theme[temp_end]="{{ colors.danger }}"
             base16: themes_path.clone(),
             base24: themes_path.clone(),
             ansi16: themes_path,
+            fill_missing: false,
         }),
+        auto: None,
+        hooks: None,
     }
 }
 
@@ -315,6 +321,124 @@ fn test_render_variant_uses_cache() {
     assert_eq!(content, "MODIFIED");
 }
 
+#[test]
+fn test_render_variant_rerenders_edited_theme_source() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+    let path = cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+
+    // Edit the theme source behind the cache's back
+    let source = temp_dir.path().join("themes/test-theme/dark.toml");
+    fs::write(
+        &source,
+        TEST_THEME_TOML.replace("base00 = \"#000000\"", "base00 = \"#101010\""),
+    )
+    .unwrap();
+    assert_eq!(
+        cache.status(&Scheme::Vogix16, "test-theme", "dark"),
+        CacheStatus::Stale
+    );
+
+    cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+
+    let content = fs::read_to_string(path.join("test.toml")).unwrap();
+    assert_eq!(content, "background = \"#101010\"");
+    assert!(cache.is_cached(&Scheme::Vogix16, "test-theme", "dark"));
+    // The stale variant was swapped out, not left next to the new one
+    assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_status_stale_after_template_edit() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+    cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+
+    fs::write(
+        temp_dir.path().join("templates/vogix16/test.toml.vogix"),
+        "bg = \"{{ colors.background }}\"",
+    )
+    .unwrap();
+
+    assert_eq!(
+        cache.status(&Scheme::Vogix16, "test-theme", "dark"),
+        CacheStatus::Stale
+    );
+}
+
+#[test]
+fn test_status_of_incomplete_and_orphaned_variants() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+    assert_eq!(
+        cache.status(&Scheme::Vogix16, "test-theme", "dark"),
+        CacheStatus::Missing
+    );
+
+    // No completion marker, e.g. a render that was interrupted
+    fs::create_dir_all(cache.variant_cache_path(&Scheme::Vogix16, "test-theme", "dark")).unwrap();
+    assert_eq!(
+        cache.status(&Scheme::Vogix16, "test-theme", "dark"),
+        CacheStatus::Incomplete
+    );
+    assert!(!cache.is_cached(&Scheme::Vogix16, "test-theme", "dark"));
+
+    cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+    fs::remove_file(temp_dir.path().join("themes/test-theme/dark.toml")).unwrap();
+    assert_eq!(
+        cache.status(&Scheme::Vogix16, "test-theme", "dark"),
+        CacheStatus::SourceMissing
+    );
+}
+
+#[test]
+fn test_verify_reports_each_rendered_variant() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+    cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+    let unhashed = cache.variant_cache_path(&Scheme::Vogix16, "old-theme", "light");
+    fs::create_dir_all(&unhashed).unwrap();
+    // Staging directories are not variants
+    fs::create_dir_all(unhashed.with_file_name(".light.staging-1")).unwrap();
+
+    let variants = cache.verify().unwrap();
+
+    let summary: Vec<_> = variants
+        .iter()
+        .map(|v| format!("{}/{}/{} {}", v.scheme, v.theme, v.variant, v.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "vogix16/old-theme/light incomplete",
+            "vogix16/test-theme/dark fresh"
+        ]
+    );
+}
+
+#[test]
+fn test_verify_empty_cache() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+
+    assert!(cache.verify().unwrap().is_empty());
+}
+
 #[test]
 fn test_render_variant_missing_theme() {
     let temp_dir = TempDir::new().unwrap();
@@ -335,6 +459,47 @@ fn test_render_variant_missing_variant() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_render_variant_failure_leaves_nothing_cached() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+    fs::write(
+        temp_dir.path().join("templates/vogix16/broken.conf.vogix"),
+        "color = {{ colors.background",
+    )
+    .unwrap();
+
+    let result = cache.render_variant(&Scheme::Vogix16, "test-theme", "dark");
+    assert!(result.is_err());
+    assert!(!cache.is_cached(&Scheme::Vogix16, "test-theme", "dark"));
+
+    // The staging directory is cleaned up too
+    let theme_dir = cache
+        .variant_cache_path(&Scheme::Vogix16, "test-theme", "dark")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    assert_eq!(fs::read_dir(&theme_dir).unwrap().count(), 0);
+}
+
+#[test]
+fn test_render_variant_leaves_no_staging_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_test_config(&temp_dir);
+    let cache = create_test_cache(&temp_dir, &config);
+
+    let path = cache
+        .render_variant(&Scheme::Vogix16, "test-theme", "dark")
+        .unwrap();
+
+    let entries: Vec<_> = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, vec!["dark"]);
+}
+
 #[test]
 fn test_clean_stale_removes_old_hashes() {
     let temp_dir = TempDir::new().unwrap();
@@ -1,5 +1,6 @@
 use crate::scheme::Scheme;
 use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 
 #[derive(Parser)]
 #[command(name = env!("CARGO_PKG_NAME"))]
@@ -26,6 +27,10 @@ pub struct Cli {
     /// Suppress non-error output
     #[arg(short = 'q', long, global = true)]
     pub quiet: bool,
+
+    /// Output format for list, status and show
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -45,6 +50,15 @@ pub enum Commands {
     /// Show current theme and variant status
     Status,
 
+    /// Print a theme's palette with color swatches (variant with -v)
+    Show {
+        /// Theme name
+        theme: String,
+    },
+
+    /// Pick a theme interactively with a live preview (filter with -s)
+    Pick,
+
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for
@@ -59,12 +73,58 @@ pub enum Commands {
 
     /// Refresh current theme (reapply without changes)
     Refresh,
+
+    /// Switch between light and dark variants on the [auto] schedule
+    Auto {
+        /// Apply the scheduled polarity once and exit instead of running as a daemon
+        #[arg(long)]
+        apply_now: bool,
+    },
+
+    /// Serve theme changes, status and theme lists over $XDG_RUNTIME_DIR/vogix.sock
+    Daemon,
+
+    /// Print a JSON line for the current theme and each change, for bars and widgets
+    Watch,
+
+    /// Show recent theme changes (* marks the applied entry)
+    History,
+
+    /// Reapply the previous theme from history
+    Undo,
+
+    /// Reapply the theme undone last
+    Redo,
+
+    /// Inspect the vogix configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum CacheCommands {
     /// Remove stale cache entries from old template versions
     Clean,
+    /// Report cached variants that no longer match their templates or theme sources
+    Verify,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Validate the manifest, theme sources, templates and app config paths
+    Check,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// JSON document
+    Json,
+    /// TOML document
+    Toml,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -91,6 +151,28 @@ impl Cli {
         self.scheme.is_some() || self.theme.is_some() || self.variant.is_some()
     }
 
+    /// The theme change flags (-s, -t, -v) as a request
+    pub fn theme_request(&self) -> ThemeRequest {
+        ThemeRequest {
+            scheme: self.scheme,
+            theme: self.theme.clone(),
+            variant: self.variant.clone(),
+        }
+    }
+}
+
+/// A theme change as given by the -s, -t and -v flags
+///
+/// Also sent as-is to a running daemon.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThemeRequest {
+    pub scheme: Option<Scheme>,
+    pub theme: Option<String>,
+    /// Variant name, polarity (dark/light) or navigation (darker/lighter)
+    pub variant: Option<String>,
+}
+
+impl ThemeRequest {
     /// Check if variant is a navigation command (darker/lighter)
     pub fn is_variant_navigation(&self) -> bool {
         if let Some(ref v) = self.variant {
@@ -112,6 +194,7 @@ mod tests {
             theme: theme.map(String::from),
             variant: variant.map(String::from),
             quiet: false,
+            format: OutputFormat::Text,
         }
     }
 
@@ -148,39 +231,39 @@ mod tests {
     #[test]
     fn test_is_variant_navigation_darker() {
         let cli = cli_with_flags(None, None, Some("darker"));
-        assert!(cli.is_variant_navigation());
+        assert!(cli.theme_request().is_variant_navigation());
     }
 
     #[test]
     fn test_is_variant_navigation_lighter() {
         let cli = cli_with_flags(None, None, Some("lighter"));
-        assert!(cli.is_variant_navigation());
+        assert!(cli.theme_request().is_variant_navigation());
     }
 
     #[test]
     fn test_is_variant_navigation_case_insensitive() {
         let cli = cli_with_flags(None, None, Some("DARKER"));
-        assert!(cli.is_variant_navigation());
+        assert!(cli.theme_request().is_variant_navigation());
 
         let cli = cli_with_flags(None, None, Some("Lighter"));
-        assert!(cli.is_variant_navigation());
+        assert!(cli.theme_request().is_variant_navigation());
     }
 
     #[test]
     fn test_is_variant_navigation_normal_variant() {
         let cli = cli_with_flags(None, None, Some("dark"));
-        assert!(!cli.is_variant_navigation());
+        assert!(!cli.theme_request().is_variant_navigation());
 
         let cli = cli_with_flags(None, None, Some("light"));
-        assert!(!cli.is_variant_navigation());
+        assert!(!cli.theme_request().is_variant_navigation());
 
         let cli = cli_with_flags(None, None, Some("moon"));
-        assert!(!cli.is_variant_navigation());
+        assert!(!cli.theme_request().is_variant_navigation());
     }
 
     #[test]
     fn test_is_variant_navigation_none() {
         let cli = cli_with_flags(None, None, None);
-        assert!(!cli.is_variant_navigation());
+        assert!(!cli.theme_request().is_variant_navigation());
     }
 }
@@ -0,0 +1,548 @@
+//! Color values and conversions for displaying theme palettes and deriving shades.
+//!
+//! Theme loaders parse every color into a `Color`; conversions work on its
+//! `(r, g, b)` byte triple. Shades are derived in OKLab and its polar form
+//! OKLCH, where equal steps look like equal changes.
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// xterm 6x6x6 color cube channel levels (palette indices 16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colors by name, with xterm's default values
+const ANSI16: [(&str, (u8, u8, u8)); 16] = [
+    ("black", (0x00, 0x00, 0x00)),
+    ("red", (0xcd, 0x00, 0x00)),
+    ("green", (0x00, 0xcd, 0x00)),
+    ("yellow", (0xcd, 0xcd, 0x00)),
+    ("blue", (0x00, 0x00, 0xee)),
+    ("magenta", (0xcd, 0x00, 0xcd)),
+    ("cyan", (0x00, 0xcd, 0xcd)),
+    ("white", (0xe5, 0xe5, 0xe5)),
+    ("brightblack", (0x7f, 0x7f, 0x7f)),
+    ("brightred", (0xff, 0x00, 0x00)),
+    ("brightgreen", (0x00, 0xff, 0x00)),
+    ("brightyellow", (0xff, 0xff, 0x00)),
+    ("brightblue", (0x5c, 0x5c, 0xff)),
+    ("brightmagenta", (0xff, 0x00, 0xff)),
+    ("brightcyan", (0x00, 0xff, 0xff)),
+    ("brightwhite", (0xff, 0xff, 0xff)),
+];
+
+/// A theme color, normalized on load
+///
+/// Displays and serializes as `#rrggbb`, or `#rrggbbaa` if not fully opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    /// An opaque color
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Parse `#rgb`, `#rrggbb` or `#rrggbbaa` (`#` optional), or `rgb(r, g, b)`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(channels) = value
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let channels: Vec<u8> = channels
+                .split(',')
+                .map(|channel| channel.trim().parse().ok())
+                .collect::<Option<_>>()?;
+            return match channels[..] {
+                [r, g, b] => Some(Color::rgb(r, g, b)),
+                _ => None,
+            };
+        }
+
+        let digits = value.strip_prefix('#').unwrap_or(value);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+        match digits.len() {
+            3 => {
+                let short = |i: usize| u8::from_str_radix(&digits[i..=i], 16).ok().map(|c| c * 17);
+                Some(Color::rgb(short(0)?, short(1)?, short(2)?))
+            }
+            6 => Some(Color::rgb(channel(0)?, channel(2)?, channel(4)?)),
+            8 => Some(Color {
+                a: channel(6)?,
+                ..Color::rgb(channel(0)?, channel(2)?, channel(4)?)
+            }),
+            _ => None,
+        }
+    }
+
+    /// The color's RGB components, without alpha
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::rgb(r, g, b)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)?;
+        if self.a != 255 {
+            write!(f, "{:02x}", self.a)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Convert RGB to HSL as (hue in degrees, saturation %, lightness %)
+pub fn to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let [r, g, b] = [r, g, b].map(|c| f64::from(c) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness * 100.0);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+
+    (hue * 60.0, saturation * 100.0, lightness * 100.0)
+}
+
+/// Convert RGB to HSV as (hue in degrees, saturation %, value %)
+pub fn to_hsv((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (hue, _, _) = to_hsl((r, g, b));
+    let max = f64::from(r.max(g).max(b)) / 255.0;
+    let min = f64::from(r.min(g).min(b)) / 255.0;
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+    (hue, saturation * 100.0, max * 100.0)
+}
+
+/// WCAG relative luminance (0.0 = black, 1.0 = white)
+pub fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    0.2126 * to_linear(r) + 0.7152 * to_linear(g) + 0.0722 * to_linear(b)
+}
+
+/// Convert RGB to OKLab as (lightness 0.0-1.0, a, b)
+pub fn to_oklab((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let [r, g, b] = [r, g, b].map(to_linear);
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+/// Convert OKLab to RGB, clamping channels outside the sRGB gamut
+pub fn from_oklab(lab: (f64, f64, f64)) -> (u8, u8, u8) {
+    let [r, g, b] = oklab_to_linear(lab).map(from_linear);
+    (r, g, b)
+}
+
+/// Convert RGB to OKLCH as (lightness 0.0-1.0, chroma, hue in degrees)
+pub fn to_oklch(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (l, a, b) = to_oklab(rgb);
+    (l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0))
+}
+
+/// Convert OKLCH to RGB
+///
+/// Colors outside the sRGB gamut lose chroma until they fit, keeping their
+/// lightness and hue.
+pub fn from_oklch((l, c, h): (f64, f64, f64)) -> (u8, u8, u8) {
+    let l = l.clamp(0.0, 1.0);
+    let lab = |c: f64| {
+        let (sin, cos) = h.to_radians().sin_cos();
+        (l, c * cos, c * sin)
+    };
+    let in_gamut = |c: f64| {
+        oklab_to_linear(lab(c))
+            .iter()
+            .all(|channel| (-1e-6..=1.0 + 1e-6).contains(channel))
+    };
+
+    if in_gamut(c) {
+        return from_oklab(lab(c));
+    }
+    let (mut low, mut high) = (0.0, c.max(0.0));
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    from_oklab(lab(low))
+}
+
+/// Shift OKLCH lightness by `amount` (-1.0 to 1.0)
+pub fn lighten(rgb: (u8, u8, u8), amount: f64) -> (u8, u8, u8) {
+    let (l, c, h) = to_oklch(rgb);
+    from_oklch((l + amount, c, h))
+}
+
+/// Scale OKLCH chroma by `1.0 + amount`, so -1.0 gives gray
+pub fn saturate(rgb: (u8, u8, u8), amount: f64) -> (u8, u8, u8) {
+    let (l, c, h) = to_oklch(rgb);
+    from_oklch((l, (c * (1.0 + amount)).max(0.0), h))
+}
+
+/// Interpolate from `a` (t = 0.0) to `b` (t = 1.0) in OKLab
+pub fn mix(a: (u8, u8, u8), b: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let (a, b) = (to_oklab(a), to_oklab(b));
+    let lerp = |x: f64, y: f64| x + (y - x) * t;
+    from_oklab((lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2)))
+}
+
+/// `n` evenly spaced colors from `a` to `b`, both included
+pub fn ramp(a: (u8, u8, u8), b: (u8, u8, u8), n: usize) -> Vec<(u8, u8, u8)> {
+    match n {
+        0 => Vec::new(),
+        1 => vec![a],
+        _ => (0..n)
+            .map(|i| mix(a, b, i as f64 / (n - 1) as f64))
+            .collect(),
+    }
+}
+
+/// Index of the candidate whose OKLab lightness differs most from `background`
+///
+/// Ties go to the earlier candidate; None if there are no candidates.
+pub fn most_readable(background: (u8, u8, u8), candidates: &[(u8, u8, u8)]) -> Option<usize> {
+    let (background, _, _) = to_oklab(background);
+    let contrast = |rgb: &(u8, u8, u8)| (to_oklab(*rgb).0 - background).abs();
+    candidates
+        .iter()
+        .enumerate()
+        .fold(None, |best: Option<(usize, f64)>, (i, rgb)| {
+            let contrast = contrast(rgb);
+            match best {
+                Some((_, best_contrast)) if best_contrast >= contrast => best,
+                _ => Some((i, contrast)),
+            }
+        })
+        .map(|(i, _)| i)
+}
+
+/// sRGB channel to linear light (0.0-1.0)
+fn to_linear(c: u8) -> f64 {
+    let c = f64::from(c) / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Linear light to an sRGB channel, clamped to 0-255
+fn from_linear(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+/// OKLab to linear sRGB channels, which may fall outside 0.0-1.0
+fn oklab_to_linear((l, a, b): (f64, f64, f64)) -> [f64; 3] {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+}
+
+/// Nearest xterm 256-color palette index
+///
+/// Only the color cube and grayscale ramp are considered; indices 0-15 are
+/// defined by the terminal's own theme and can't be relied on.
+pub fn nearest_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let nearest_level = |c: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| c.abs_diff(**level))
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (
+        nearest_level(rgb.0),
+        nearest_level(rgb.1),
+        nearest_level(rgb.2),
+    );
+    let cube = (
+        CUBE_LEVELS[usize::from(ri)],
+        CUBE_LEVELS[usize::from(gi)],
+        CUBE_LEVELS[usize::from(bi)],
+    );
+
+    // Grayscale ramp 232-255 covers 8, 18, ..., 238
+    let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + gray_step * 10;
+
+    if distance(rgb, (gray_level, gray_level, gray_level)) < distance(rgb, cube) {
+        232 + gray_step
+    } else {
+        16 + 36 * ri + 6 * gi + bi
+    }
+}
+
+/// Name of the nearest of the 16 ANSI colors, as xterm draws them by default
+pub fn nearest_ansi16(rgb: (u8, u8, u8)) -> &'static str {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map(|(name, _)| *name)
+        .unwrap_or("black")
+}
+
+/// Squared euclidean distance in RGB space
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_parse() {
+        assert_eq!(Color::parse("#1e1e2e"), Some(Color::rgb(0x1e, 0x1e, 0x2e)));
+        assert_eq!(Color::parse("FFFFFF"), Some(Color::rgb(255, 255, 255)));
+        assert_eq!(Color::parse("#f80"), Some(Color::rgb(0xff, 0x88, 0x00)));
+        assert_eq!(
+            Color::parse("#1e1e2e80"),
+            Some(Color {
+                a: 0x80,
+                ..Color::rgb(0x1e, 0x1e, 0x2e)
+            })
+        );
+        assert_eq!(
+            Color::parse("rgb(30, 30,46)"),
+            Some(Color::rgb(0x1e, 0x1e, 0x2e))
+        );
+    }
+
+    #[test]
+    fn test_color_parse_invalid() {
+        for invalid in [
+            "",
+            "#",
+            "#12345",
+            "#gggggg",
+            "#1e1e2e8",
+            "rgb(1,2)",
+            "rgb(1,2,256)",
+            "red",
+            "#ééé",
+        ] {
+            assert_eq!(Color::parse(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_color_display() {
+        assert_eq!(Color::parse("#1E1E2E").unwrap().to_string(), "#1e1e2e");
+        assert_eq!(Color::parse("#1e1e2eff").unwrap().to_string(), "#1e1e2e");
+        assert_eq!(
+            serde_json::to_string(&Color::rgb(0, 0, 255)).unwrap(),
+            r##""#0000ff""##
+        );
+    }
+
+    #[test]
+    fn test_color_display_keeps_alpha() {
+        let translucent = Color::parse("1e1e2e80").unwrap();
+        assert_eq!(translucent.to_string(), "#1e1e2e80");
+        assert_eq!(
+            serde_json::to_string(&translucent).unwrap(),
+            r##""#1e1e2e80""##
+        );
+    }
+
+    #[test]
+    fn test_to_hsl_primaries() {
+        assert_eq!(to_hsl((255, 0, 0)), (0.0, 100.0, 50.0));
+        assert_eq!(to_hsl((0, 255, 0)), (120.0, 100.0, 50.0));
+        assert_eq!(to_hsl((0, 0, 255)), (240.0, 100.0, 50.0));
+    }
+
+    #[test]
+    fn test_to_hsl_gray_has_no_hue() {
+        let (h, s, l) = to_hsl((128, 128, 128));
+        assert_eq!((h, s), (0.0, 0.0));
+        assert!((l - 50.2).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_to_hsl_negative_hue_wraps() {
+        // Magenta-ish red: max is red with blue > green
+        let (h, _, _) = to_hsl((255, 0, 128));
+        assert!((h - 329.9).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_to_hsv() {
+        assert_eq!(to_hsv((255, 0, 0)), (0.0, 100.0, 100.0));
+        assert_eq!(to_hsv((0, 0, 0)), (0.0, 0.0, 0.0));
+        let (h, s, v) = to_hsv((255, 87, 51));
+        assert!((h - 10.6).abs() < 0.1);
+        assert!((s - 80.0).abs() < 0.1);
+        assert_eq!(v, 100.0);
+    }
+
+    #[test]
+    fn test_relative_luminance_bounds() {
+        assert_eq!(relative_luminance((0, 0, 0)), 0.0);
+        assert!((relative_luminance((255, 255, 255)) - 1.0).abs() < 1e-9);
+        assert!((relative_luminance((255, 0, 0)) - 0.2126).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_nearest_ansi256_cube() {
+        assert_eq!(nearest_ansi256((255, 0, 0)), 196);
+        assert_eq!(nearest_ansi256((0, 0, 0)), 16);
+        assert_eq!(nearest_ansi256((255, 255, 255)), 231);
+        assert_eq!(nearest_ansi256((100, 140, 210)), 16 + 36 + 6 * 2 + 4);
+    }
+
+    #[test]
+    fn test_nearest_ansi256_grayscale() {
+        assert_eq!(nearest_ansi256((8, 8, 8)), 232);
+        assert_eq!(nearest_ansi256((30, 30, 30)), 234);
+        assert_eq!(nearest_ansi256((238, 238, 238)), 255);
+    }
+
+    #[test]
+    fn test_nearest_ansi16() {
+        assert_eq!(nearest_ansi16((0, 0, 0)), "black");
+        assert_eq!(nearest_ansi16((0xd0, 0x10, 0x10)), "red");
+        assert_eq!(nearest_ansi16((0xfb, 0x49, 0x34)), "brightred");
+        assert_eq!(nearest_ansi16((0xb8, 0xbb, 0x26)), "yellow");
+        assert_eq!(nearest_ansi16((0xfb, 0xf1, 0xc7)), "white");
+    }
+
+    #[test]
+    fn test_oklab_reference_values() {
+        let (l, a, b) = to_oklab((255, 255, 255));
+        assert!((l - 1.0).abs() < 1e-4 && a.abs() < 1e-4 && b.abs() < 1e-4);
+        assert_eq!(to_oklab((0, 0, 0)), (0.0, 0.0, 0.0));
+
+        // Red is (0.6280, 0.2249, 0.1258) in the OKLab reference
+        let (l, a, b) = to_oklab((255, 0, 0));
+        assert!((l - 0.6280).abs() < 1e-3);
+        assert!((a - 0.2249).abs() < 1e-3);
+        assert!((b - 0.1258).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        for rgb in [
+            (0, 0, 0),
+            (255, 255, 255),
+            (0x28, 0x28, 0x28),
+            (0xfb, 0x49, 0x34),
+        ] {
+            assert_eq!(from_oklab(to_oklab(rgb)), rgb);
+            assert_eq!(from_oklch(to_oklch(rgb)), rgb);
+        }
+    }
+
+    #[test]
+    fn test_from_oklch_reduces_chroma_out_of_gamut() {
+        // Far more chroma than sRGB can show at this lightness
+        let (r, g, b) = from_oklch((0.9, 0.4, 30.0));
+        let (l, _, h) = to_oklch((r, g, b));
+        assert!((l - 0.9).abs() < 0.01);
+        assert!((h - 30.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_lighten_and_darken() {
+        let gray = (0x80, 0x80, 0x80);
+        let (lighter, _, _) = to_oklch(lighten(gray, 0.1));
+        let (darker, _, _) = to_oklch(lighten(gray, -0.1));
+        let (l, _, _) = to_oklch(gray);
+        assert!((lighter - l - 0.1).abs() < 0.01);
+        assert!((l - darker - 0.1).abs() < 0.01);
+
+        assert_eq!(lighten(gray, 1.0), (255, 255, 255));
+        assert_eq!(lighten(gray, -1.0), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_saturate() {
+        let muted = (0x83, 0xa5, 0x98);
+        let (_, c, _) = to_oklch(muted);
+        let (_, more, _) = to_oklch(saturate(muted, 0.5));
+        assert!(more > c);
+
+        let (r, g, b) = saturate(muted, -1.0);
+        assert!(r.abs_diff(g) <= 1 && g.abs_diff(b) <= 1);
+    }
+
+    #[test]
+    fn test_mix_and_ramp() {
+        let (black, white) = ((0, 0, 0), (255, 255, 255));
+        assert_eq!(mix(black, white, 0.0), black);
+        assert_eq!(mix(black, white, 1.0), white);
+        // Perceptual midpoint, lighter than the RGB average of 128
+        assert_eq!(mix(black, white, 0.5), (99, 99, 99));
+
+        let ramp = ramp(black, white, 5);
+        assert_eq!(ramp.len(), 5);
+        assert_eq!((ramp[0], ramp[4]), (black, white));
+        assert!(ramp.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_ramp_short() {
+        assert!(ramp((0, 0, 0), (255, 255, 255), 0).is_empty());
+        assert_eq!(ramp((1, 2, 3), (255, 255, 255), 1), vec![(1, 2, 3)]);
+    }
+
+    #[test]
+    fn test_most_readable() {
+        let candidates = [(0x3c, 0x38, 0x36), (0xeb, 0xdb, 0xb2), (0x92, 0x83, 0x74)];
+        assert_eq!(most_readable((0x28, 0x28, 0x28), &candidates), Some(1));
+        assert_eq!(most_readable((0xfb, 0xf1, 0xc7), &candidates), Some(0));
+        assert_eq!(most_readable((0, 0, 0), &[]), None);
+    }
+}
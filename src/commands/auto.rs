@@ -0,0 +1,148 @@
+//! Auto command - switch between light and dark variants on a schedule.
+
+use crate::config::Config;
+use crate::errors::{Result, VogixError};
+use crate::schedule::Schedule;
+use crate::state::{AppliedBy, State};
+use crate::theme::{self, types::ThemeInfo};
+use chrono::Local;
+use log::{debug, info, warn};
+use std::thread;
+use std::time::Duration;
+
+use super::history::record_change;
+use super::theme_change::apply_state;
+
+/// Seconds between checks when `[auto] interval` is not set
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+/// Handle the `auto` command - apply the scheduled polarity once or keep it applied
+///
+/// In daemon mode the variant is only switched when the scheduled polarity
+/// changes, so a manual `vogix -v` lasts until the next sunrise or sunset.
+pub fn handle_auto(apply_now: bool, quiet: bool) -> Result<()> {
+    let config = Config::load()?;
+    let auto = config
+        .auto
+        .as_ref()
+        .ok_or_else(|| VogixError::Config("no [auto] section in manifest".to_string()))?;
+    let schedule =
+        Schedule::from_config(auto).map_err(|e| VogixError::Config(format!("[auto] {}", e)))?;
+
+    if apply_now {
+        return apply_polarity(schedule.polarity_at(&Local::now()), &config, quiet);
+    }
+
+    let interval = Duration::from_secs(auto.interval.unwrap_or(DEFAULT_INTERVAL_SECS).max(1));
+    info!("Switching polarity automatically: {}", schedule);
+
+    let mut last_polarity = None;
+    loop {
+        let polarity = schedule.polarity_at(&Local::now());
+        if last_polarity != Some(polarity) {
+            match apply_polarity(polarity, &config, quiet) {
+                Ok(()) => last_polarity = Some(polarity),
+                // Retried on the next check
+                Err(e) => warn!("Failed to switch to {} variant: {}", polarity, e),
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Switch the current theme to its default variant for `polarity`
+fn apply_polarity(polarity: &str, config: &Config, quiet: bool) -> Result<()> {
+    // Held until the change is applied and recorded, like a manual switch
+    let lock = State::lock()?;
+    let mut state = State::load()?;
+    let themes = theme::discover_themes()?;
+    let theme_info = theme::get_theme(&themes, &state.current_theme).ok_or_else(|| {
+        VogixError::InvalidTheme(format!("Theme '{}' not found", state.current_theme))
+    })?;
+
+    let Some(variant) = variant_for_polarity(&theme_info, &state.current_variant, polarity) else {
+        debug!(
+            "{}-{} is already {}",
+            state.current_theme, state.current_variant, polarity
+        );
+        return Ok(());
+    };
+
+    let previous = state.clone();
+    state.current_variant = variant;
+    info!(
+        "variant: {} → {} ({})",
+        previous.current_variant, state.current_variant, polarity
+    );
+    apply_state(&state, &previous, config, &themes, AppliedBy::Auto, quiet)?;
+    record_change(&lock, &previous, &state);
+    Ok(())
+}
+
+/// Variant to switch to, or `None` if `current` already has `polarity`
+///
+/// Themes without a variant of that polarity stay on their current variant.
+fn variant_for_polarity(theme_info: &ThemeInfo, current: &str, polarity: &str) -> Option<String> {
+    let current_polarity = theme_info
+        .variants
+        .iter()
+        .find(|v| v.name == current)
+        .map(|v| v.polarity.as_str());
+    if current_polarity == Some(polarity) {
+        return None;
+    }
+
+    theme_info
+        .default_variant_for_polarity(polarity)
+        .filter(|v| v.polarity == polarity && v.name != current)
+        .map(|v| v.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::Scheme;
+    use crate::theme::types::VariantInfo;
+
+    fn theme_info(variants: &[(&str, &str)]) -> ThemeInfo {
+        ThemeInfo {
+            name: "catppuccin".to_string(),
+            scheme: Scheme::Base24,
+            variants: variants
+                .iter()
+                .enumerate()
+                .map(|(order, (name, polarity))| VariantInfo {
+                    name: name.to_string(),
+                    polarity: polarity.to_string(),
+                    order: order as u32,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_variant_for_polarity_switches() {
+        let theme = theme_info(&[("latte", "light"), ("frappe", "dark"), ("mocha", "dark")]);
+        assert_eq!(
+            variant_for_polarity(&theme, "mocha", "light"),
+            Some("latte".to_string())
+        );
+        assert_eq!(
+            variant_for_polarity(&theme, "latte", "dark"),
+            Some("frappe".to_string())
+        );
+    }
+
+    #[test]
+    fn test_variant_for_polarity_keeps_matching_variant() {
+        // mocha is dark already, even though frappe is the default dark variant
+        let theme = theme_info(&[("latte", "light"), ("frappe", "dark"), ("mocha", "dark")]);
+        assert_eq!(variant_for_polarity(&theme, "mocha", "dark"), None);
+    }
+
+    #[test]
+    fn test_variant_for_polarity_missing_polarity() {
+        let theme = theme_info(&[("dark", "dark")]);
+        assert_eq!(variant_for_polarity(&theme, "dark", "light"), None);
+    }
+}
@@ -1,21 +1,14 @@
 //! Cache management command handlers.
 
-use crate::cache::ThemeCache;
+use crate::cache::{CacheStatus, ThemeCache};
 use crate::config::Config;
-use crate::errors::Result;
+use crate::errors::{Result, VogixError};
 
 /// Handle the `cache clean` command - remove stale cache entries
 pub fn handle_cache_clean() -> Result<()> {
-    let config = Config::load()?;
-
-    // Check if template caching is configured
-    if config.templates.is_none() {
-        println!("Template caching is not configured.");
-        println!("Add a [templates] section to your config to enable caching.");
+    let Some(cache) = load_cache()? else {
         return Ok(());
-    }
-
-    let cache = ThemeCache::from_config(&config)?;
+    };
     let removed = cache.clean_stale()?;
 
     if removed == 0 {
@@ -26,3 +19,59 @@ pub fn handle_cache_clean() -> Result<()> {
 
     Ok(())
 }
+
+/// Handle the `cache verify` command - report variants rendered from outdated sources
+///
+/// Mismatched variants are re-rendered the next time they are applied.
+pub fn handle_cache_verify() -> Result<()> {
+    let Some(cache) = load_cache()? else {
+        return Ok(());
+    };
+    let variants = cache.verify()?;
+
+    let mismatched: Vec<_> = variants
+        .iter()
+        .filter(|v| v.status != CacheStatus::Fresh)
+        .collect();
+    for v in &mismatched {
+        let reason = match v.status {
+            CacheStatus::Stale => "templates or theme source changed since rendering",
+            CacheStatus::Incomplete => {
+                "no completion marker, render was interrupted or predates source hashes"
+            }
+            CacheStatus::SourceMissing => "theme source no longer exists",
+            CacheStatus::Fresh | CacheStatus::Missing => "",
+        };
+        println!(
+            "{}: {}/{}/{} ({})",
+            v.status, v.scheme, v.theme, v.variant, reason
+        );
+    }
+
+    if mismatched.is_empty() {
+        println!(
+            "All {} cached variants match their sources.",
+            variants.len()
+        );
+        return Ok(());
+    }
+    Err(VogixError::Cache(format!(
+        "{} of {} cached variants don't match their sources",
+        mismatched.len(),
+        variants.len()
+    )))
+}
+
+/// The template cache, or None after explaining that caching is not configured
+fn load_cache() -> Result<Option<ThemeCache>> {
+    let config = Config::load()?;
+
+    // Check if template caching is configured
+    if config.templates.is_none() {
+        println!("Template caching is not configured.");
+        println!("Add a [templates] section to your config to enable caching.");
+        return Ok(None);
+    }
+
+    ThemeCache::from_config(&config).map(Some)
+}
@@ -0,0 +1,646 @@
+//! Config command handlers - validate the manifest.
+
+use crate::cache::{COMMON_TEMPLATES_DIR, theme_variant_path};
+use crate::config::{Config, TemplatesConfig, ThemeSourcesConfig};
+use crate::errors::{Result, VogixError};
+use crate::schedule::Schedule;
+use crate::scheme::Scheme;
+use crate::theme::{load_theme_colors, parse_themes_from_manifest};
+use std::ffi::CString;
+use std::fmt;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// Schemes that need a template directory under `[templates] path`, unless it has a common one
+const SCHEMES: [Scheme; 4] = [
+    Scheme::Vogix16,
+    Scheme::Base16,
+    Scheme::Base24,
+    Scheme::Ansi16,
+];
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single finding about the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Diagnostic {
+    severity: Severity,
+    /// Manifest key the finding is about (e.g. "apps.btop", "themes.aikido")
+    subject: String,
+    message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: [{}] {}", label, self.subject, self.message)
+    }
+}
+
+/// Every diagnostic found while checking a manifest
+#[derive(Debug, Default)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    fn error(&mut self, subject: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, subject.into(), message.into());
+    }
+
+    fn warning(&mut self, subject: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, subject.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, subject: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            subject,
+            message,
+        });
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+}
+
+/// Read the manifest, reporting only a missing file as not found
+fn read_manifest(manifest_path: &Path) -> Result<String> {
+    fs::read_to_string(manifest_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => VogixError::ConfigNotFound(manifest_path.to_path_buf()),
+        _ => VogixError::Io(e),
+    })
+}
+
+/// Handle the `config check` command - validate the manifest and everything it references
+///
+/// Prints every diagnostic found and fails if any of them is an error.
+pub fn handle_config_check(quiet: bool) -> Result<()> {
+    let manifest_path = Config::manifest_path()?;
+    let report = check_manifest(&read_manifest(&manifest_path)?);
+
+    for diagnostic in &report.diagnostics {
+        match diagnostic.severity {
+            Severity::Error => eprintln!("{}", diagnostic),
+            Severity::Warning => println!("{}", diagnostic),
+        }
+    }
+
+    let errors = report.count(Severity::Error);
+    let warnings = report.count(Severity::Warning);
+    if errors > 0 {
+        return Err(VogixError::Config(format!(
+            "{}: {} error(s), {} warning(s)",
+            manifest_path.display(),
+            errors,
+            warnings
+        )));
+    }
+
+    if !quiet {
+        println!(
+            "✓ {} is valid ({} warning(s))",
+            manifest_path.display(),
+            warnings
+        );
+    }
+    Ok(())
+}
+
+/// Check manifest contents without falling back to defaults
+fn check_manifest(contents: &str) -> Report {
+    let mut report = Report::default();
+
+    let manifest: toml::Value = match toml::from_str(contents) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            report.error("manifest", e.message());
+            return report;
+        }
+    };
+
+    let default_theme = check_string(&manifest, "default", "theme", &mut report);
+    let default_variant = check_string(&manifest, "default", "variant", &mut report);
+
+    let apps = match Config::parse_apps(contents) {
+        Ok(apps) => apps,
+        Err(issues) => {
+            for issue in issues {
+                report.error(
+                    issue.key,
+                    format!(
+                        "line {}, column {}: {}",
+                        issue.line, issue.column, issue.message
+                    ),
+                );
+            }
+            Default::default()
+        }
+    };
+
+    match Config::parse_auto(&manifest) {
+        Ok(Some(auto)) => {
+            if let Err(message) = Schedule::from_config(&auto) {
+                report.error("auto", message);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => report.error("auto", e.to_string()),
+    }
+
+    if let Err(e) = Config::parse_hooks(&manifest) {
+        report.error("hooks", e.to_string());
+    }
+
+    let theme_sources = check_theme_sources(&manifest, &mut report);
+    let templates = check_templates(&manifest, &mut report);
+    if templates.is_some() && theme_sources.is_none() {
+        report.error(
+            "templates",
+            "template rendering requires a [theme_sources] section",
+        );
+    }
+
+    match parse_themes_from_manifest(contents) {
+        Ok(themes) => {
+            if themes.is_empty() {
+                report.warning("themes", "no themes defined");
+            }
+
+            if let (Some(theme), Some(variant)) = (&default_theme, &default_variant) {
+                let known = themes
+                    .iter()
+                    .find(|t| &t.name == theme)
+                    .is_some_and(|t| t.variants.iter().any(|v| &v.name == variant));
+                if !known {
+                    report.error(
+                        "default",
+                        format!("{}-{} is not a defined theme variant", theme, variant),
+                    );
+                }
+            }
+
+            if let Some(sources) = &theme_sources {
+                for theme in &themes {
+                    for variant in &theme.variants {
+                        let path =
+                            theme_variant_path(sources, &theme.scheme, &theme.name, &variant.name);
+                        if !path.is_file() {
+                            report.error(
+                                format!("themes.{}", theme.name),
+                                format!(
+                                    "{} variant '{}' has no source file at {}",
+                                    theme.scheme,
+                                    variant.name,
+                                    path.display()
+                                ),
+                            );
+                        } else if let Err(e) =
+                            load_theme_colors(&path, theme.scheme, sources.fill_missing)
+                        {
+                            report.error(
+                                format!("themes.{}", theme.name),
+                                format!("{} variant '{}': {}", theme.scheme, variant.name, e),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => report.error("themes", e.to_string()),
+    }
+
+    let mut app_names: Vec<&String> = apps.keys().collect();
+    app_names.sort();
+    for app_name in app_names {
+        let config_path = Path::new(&apps[app_name].config_path);
+        if let Err(reason) = check_writable(config_path) {
+            report.error(
+                format!("apps.{}", app_name),
+                format!("config_path {} {}", config_path.display(), reason),
+            );
+        }
+    }
+
+    report
+}
+
+/// Require `[section] key` to be a string
+fn check_string(
+    manifest: &toml::Value,
+    section: &str,
+    key: &str,
+    report: &mut Report,
+) -> Option<String> {
+    match manifest.get(section).and_then(|s| s.get(key)) {
+        Some(toml::Value::String(value)) => Some(value.clone()),
+        Some(other) => {
+            report.error(
+                section,
+                format!("{} must be a string, found {}", key, other.type_str()),
+            );
+            None
+        }
+        None => {
+            report.error(section, format!("missing key '{}'", key));
+            None
+        }
+    }
+}
+
+/// Check [theme_sources]: every scheme's directory must be set and exist
+fn check_theme_sources(manifest: &toml::Value, report: &mut Report) -> Option<ThemeSourcesConfig> {
+    if manifest.get("theme_sources").is_none() {
+        report.warning(
+            "theme_sources",
+            "section missing, theme source files can't be checked",
+        );
+        return None;
+    }
+
+    let sources: Vec<Option<String>> = SCHEMES
+        .iter()
+        .map(|scheme| check_string(manifest, "theme_sources", &scheme.to_string(), report))
+        .collect();
+    for (scheme, source) in SCHEMES.iter().zip(&sources) {
+        if let Some(dir) = source
+            && !Path::new(dir).is_dir()
+        {
+            report.error(
+                "theme_sources",
+                format!("{} directory {} does not exist", scheme, dir),
+            );
+        }
+    }
+
+    let fill_missing = match manifest["theme_sources"].get("fill_missing") {
+        Some(toml::Value::Boolean(fill_missing)) => *fill_missing,
+        Some(other) => {
+            report.error(
+                "theme_sources",
+                format!("fill_missing must be a boolean, found {}", other.type_str()),
+            );
+            false
+        }
+        None => false,
+    };
+
+    match sources.as_slice() {
+        [Some(vogix16), Some(base16), Some(base24), Some(ansi16)] => Some(ThemeSourcesConfig {
+            vogix16: vogix16.into(),
+            base16: base16.into(),
+            base24: base24.into(),
+            ansi16: ansi16.into(),
+            fill_missing,
+        }),
+        _ => None,
+    }
+}
+
+/// Check [templates]: path and hash set, with a template directory per scheme or a common one
+fn check_templates(manifest: &toml::Value, report: &mut Report) -> Option<TemplatesConfig> {
+    if manifest.get("templates").is_none() {
+        report.warning(
+            "templates",
+            "section missing, pre-generated configs are used instead of templates",
+        );
+        return None;
+    }
+
+    let path = check_string(manifest, "templates", "path", report);
+    let hash = check_string(manifest, "templates", "hash", report);
+    let templates = TemplatesConfig {
+        path: path?.into(),
+        hash: hash?,
+    };
+
+    // Schemes without a directory of their own render the common templates
+    if templates.path.join(COMMON_TEMPLATES_DIR).is_dir() {
+        return Some(templates);
+    }
+    for scheme in SCHEMES {
+        let dir = templates.path.join(scheme.to_string());
+        if !dir.is_dir() {
+            report.error(
+                "templates",
+                format!(
+                    "{} template directory {} does not exist",
+                    scheme,
+                    dir.display()
+                ),
+            );
+        }
+    }
+
+    Some(templates)
+}
+
+/// Check vogix can replace `path`: the file itself if it's a regular file,
+/// and always the directory holding it
+fn check_writable(path: &Path) -> std::result::Result<(), &'static str> {
+    let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        return Err("has no parent directory");
+    };
+    if !parent.is_dir() {
+        return Err("is in a directory that does not exist");
+    }
+    if !access_writable(parent) {
+        return Err("is in a directory that is not writable");
+    }
+
+    let is_regular_file = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_file());
+    if is_regular_file && !access_writable(path) {
+        return Err("is not writable");
+    }
+    Ok(())
+}
+
+/// access(2) with W_OK, using the real user's permissions
+fn access_writable(path: &Path) -> bool {
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: c_path is a valid NUL-terminated string for the duration of the call.
+    unsafe { libc::access(c_path.as_ptr(), libc::W_OK) == 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A complete vogix16 theme source
+    fn vogix16_source() -> String {
+        (0..16)
+            .map(|i| format!("base0{:X} = \"#0000{:02x}\"\n", i, i))
+            .fold("[colors]\n".to_string(), |source, line| source + &line)
+    }
+
+    /// Manifest with theme sources, templates and a config dir under `root`
+    fn write_tree(root: &Path) -> String {
+        for scheme in SCHEMES {
+            fs::create_dir_all(root.join("sources").join(scheme.to_string())).unwrap();
+            fs::create_dir_all(root.join("templates").join(scheme.to_string())).unwrap();
+        }
+        fs::create_dir_all(root.join("sources/vogix16/aikido")).unwrap();
+        fs::write(
+            root.join("sources/vogix16/aikido/night.toml"),
+            vogix16_source(),
+        )
+        .unwrap();
+        fs::write(
+            root.join("sources/vogix16/aikido/day.toml"),
+            vogix16_source(),
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("config/alacritty")).unwrap();
+
+        let root = root.display();
+        format!(
+            r##"
+[default]
+theme = "aikido"
+variant = "night"
+
+[templates]
+path = "{root}/templates"
+hash = "abc123"
+
+[theme_sources]
+vogix16 = "{root}/sources/vogix16"
+base16 = "{root}/sources/base16"
+base24 = "{root}/sources/base24"
+ansi16 = "{root}/sources/ansi16"
+
+[themes.aikido]
+scheme = "vogix16"
+variants = ["night", "day"]
+
+[apps.alacritty]
+config_path = "{root}/config/alacritty/alacritty.toml"
+reload_method = "touch"
+"##
+        )
+    }
+
+    fn errors(report: &Report) -> Vec<String> {
+        report
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_read_manifest_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("config.toml");
+        assert!(matches!(
+            read_manifest(&missing),
+            Err(VogixError::ConfigNotFound(path)) if path == missing
+        ));
+
+        // A directory exists, so it is an I/O error rather than "not found"
+        assert!(matches!(
+            read_manifest(temp_dir.path()),
+            Err(VogixError::Io(_))
+        ));
+
+        fs::write(&missing, [0xff, 0xfe]).unwrap();
+        assert!(matches!(read_manifest(&missing), Err(VogixError::Io(_))));
+    }
+
+    #[test]
+    fn test_check_valid_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path());
+
+        let report = check_manifest(&manifest);
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn test_check_reports_missing_variant_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path());
+        fs::remove_file(temp_dir.path().join("sources/vogix16/aikido/day.toml")).unwrap();
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("error: [themes.aikido] vogix16 variant 'day'"));
+    }
+
+    #[test]
+    fn test_check_reports_missing_template_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path());
+        fs::remove_dir(temp_dir.path().join("templates/ansi16")).unwrap();
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("ansi16 template directory"));
+    }
+
+    #[test]
+    fn test_check_accepts_common_templates_for_missing_scheme() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path());
+        fs::remove_dir(temp_dir.path().join("templates/ansi16")).unwrap();
+        fs::create_dir(temp_dir.path().join("templates/common")).unwrap();
+
+        assert!(errors(&check_manifest(&manifest)).is_empty());
+    }
+
+    #[test]
+    fn test_check_reports_unwritable_config_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path()).replace("config/alacritty", "missing/alacritty");
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("error: [apps.alacritty] config_path"));
+    }
+
+    #[test]
+    fn test_check_reports_every_problem() {
+        let manifest = r##"
+[default]
+theme = "aikido"
+
+[themes.aikido]
+scheme = "vogix16"
+variants = ["night"]
+
+[apps.btop]
+config_path = "/tmp/btop.conf"
+reload_method = "signal"
+
+[apps.mako]
+config_path = "/tmp/mako"
+reload_method = "restart"
+"##;
+
+        let report = check_manifest(manifest);
+        let errors = errors(&report);
+
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("[default] missing key 'variant'"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("[apps.btop] line 9, column 1"))
+        );
+        assert!(errors.iter().any(|e| e.contains("[apps.mako]")));
+        assert_eq!(report.count(Severity::Warning), 2);
+    }
+
+    #[test]
+    fn test_check_reports_unknown_default_variant() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest =
+            write_tree(temp_dir.path()).replace(r#"variant = "night""#, r#"variant = "dusk""#);
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(
+            errors,
+            vec!["error: [default] aikido-dusk is not a defined theme variant"]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_invalid_auto_schedule() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = format!(
+            "{}\n[auto]\nlight = \"07:00\"\n",
+            write_tree(temp_dir.path())
+        );
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(
+            errors,
+            vec!["error: [auto] set either light and dark times or latitude and longitude"]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_malformed_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = format!(
+            "{}\n[hooks]\npre_apply = \"notify-send themed\"\n",
+            write_tree(temp_dir.path())
+        );
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("error: [hooks] "));
+    }
+
+    #[test]
+    fn test_check_reports_incomplete_variant_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path());
+        let source = vogix16_source().replace("base0E = \"#00000e\"\n", "");
+        fs::write(
+            temp_dir.path().join("sources/vogix16/aikido/day.toml"),
+            source,
+        )
+        .unwrap();
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("error: [themes.aikido] vogix16 variant 'day': incomplete theme")
+        );
+        assert!(errors[0].ends_with("missing colors.base0E"));
+    }
+
+    #[test]
+    fn test_check_reports_non_boolean_fill_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write_tree(temp_dir.path()).replace(
+            "[theme_sources]\n",
+            "[theme_sources]\nfill_missing = \"yes\"\n",
+        );
+
+        let errors = errors(&check_manifest(&manifest));
+        assert_eq!(
+            errors,
+            vec!["error: [theme_sources] fill_missing must be a boolean, found string"]
+        );
+    }
+
+    #[test]
+    fn test_check_invalid_toml() {
+        let report = check_manifest("this is not valid toml {{{");
+        assert_eq!(report.count(Severity::Error), 1);
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("app.conf");
+        assert!(check_writable(&file).is_ok());
+
+        fs::write(&file, "").unwrap();
+        assert!(check_writable(&file).is_ok());
+
+        assert!(check_writable(&temp_dir.path().join("missing/app.conf")).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+//! CLI side of the daemon socket.
+
+use crate::errors::{Result, VogixError};
+use log::debug;
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+
+use super::protocol::{self, Request, Response};
+
+/// How long to wait for a reply; an apply waits for the slowest app reload
+const REPLY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Send a request to the running daemon
+///
+/// Returns `Ok(None)` when no daemon is listening, so the caller can run the
+/// command itself.
+pub fn send_request(request: &Request) -> Result<Option<Response>> {
+    match protocol::socket_path() {
+        Some(path) => send_request_to(&path, request),
+        None => Ok(None),
+    }
+}
+
+/// Send a request to the daemon listening on `path`
+pub fn send_request_to(path: &Path, request: &Request) -> Result<Option<Response>> {
+    let stream = match UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(e) => {
+            debug!("No daemon on {}: {}", path.display(), e);
+            return Ok(None);
+        }
+    };
+    debug!("Sending {:?} to daemon on {}", request, path.display());
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+
+    protocol::write_message(&stream, request)?;
+    let response = protocol::read_message(&mut BufReader::new(stream))?
+        .ok_or_else(|| VogixError::Daemon("connection closed without a reply".to_string()))?;
+    Ok(Some(response))
+}
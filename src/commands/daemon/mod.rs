@@ -0,0 +1,323 @@
+//! Daemon command - serve theme changes over a Unix socket.
+//!
+//! Keeps the manifest, discovered themes and template cache loaded and
+//! answers JSON requests on `$XDG_RUNTIME_DIR/vogix.sock`. Theme changes,
+//! `status` and `list` go through the daemon when it is running and are run
+//! directly otherwise. The manifest is loaded again whenever its file changes,
+//! so a home-manager switch takes effect without restarting the daemon.
+//!
+//! # Module Structure
+//! - `protocol`: request/response messages and the socket location
+//! - `client`: sending requests from the CLI
+
+mod client;
+mod protocol;
+#[cfg(test)]
+mod tests;
+
+pub use client::send_request;
+pub use protocol::{Request, Response};
+
+use crate::cache::ThemeCache;
+use crate::cli::ThemeRequest;
+use crate::config::Config;
+use crate::errors::{Result, VogixError};
+use crate::state::{AppliedBy, State};
+use crate::theme::{self, types::ThemeInfo};
+use log::{debug, info, warn};
+use std::fs;
+use std::io::BufReader;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use super::history::record_change;
+use super::refresh::template_cache;
+use super::status::StatusReport;
+use super::theme_change::{apply_state_with_cache, is_change, log_changes, resolve_request};
+
+/// How long a broadcast may block on a subscriber before it is dropped
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Handle the `daemon` command - serve requests until the process is stopped
+///
+/// Output of each apply follows the `quiet` flag of its request, not the daemon's.
+pub fn handle_daemon() -> Result<()> {
+    let path = protocol::socket_path()
+        .ok_or_else(|| VogixError::Daemon("XDG_RUNTIME_DIR is not set".to_string()))?;
+    let daemon = Daemon::load()?;
+    let listener = bind(&path)?;
+    daemon.warm_cache();
+
+    info!("Listening on {}", path.display());
+    Arc::new(daemon).serve(listener)
+}
+
+/// Bind the socket, replacing one left behind by a daemon that is gone
+fn bind(path: &Path) -> Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(VogixError::Daemon(format!(
+                "already running on {}",
+                path.display()
+            )));
+        }
+        debug!("Removing stale socket {}", path.display());
+        fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Everything the daemon loads from the manifest
+struct Loaded {
+    config: Config,
+    themes: Vec<ThemeInfo>,
+    /// None if template rendering is not configured
+    cache: Option<ThemeCache>,
+    /// The manifest file this was loaded from, None if it didn't exist
+    stamp: Option<ManifestStamp>,
+}
+
+impl Loaded {
+    fn load(manifest_path: &Path) -> Result<Self> {
+        // Taken first, so a change made while loading triggers another load
+        let stamp = ManifestStamp::of(manifest_path);
+        let config = Config::load_from(manifest_path)?;
+        let content = fs::read_to_string(manifest_path)
+            .map_err(|_| VogixError::ConfigNotFound(manifest_path.to_path_buf()))?;
+        let themes = theme::parse_themes_from_manifest(&content)?;
+        let cache = template_cache(&config)?;
+        Ok(Loaded {
+            config,
+            themes,
+            cache,
+            stamp,
+        })
+    }
+}
+
+/// Identifies a version of the manifest file
+///
+/// home-manager replaces the manifest with a symlink to a new store file, and
+/// store files all have the same mtime, so the file's identity counts too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ManifestStamp {
+    dev: u64,
+    ino: u64,
+    mtime: i64,
+    mtime_nsec: i64,
+    size: u64,
+}
+
+impl ManifestStamp {
+    /// Stamp of the file `path` resolves to, or None if it doesn't exist
+    fn of(path: &Path) -> Option<Self> {
+        fs::metadata(path).ok().map(|metadata| ManifestStamp {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+            mtime: metadata.mtime(),
+            mtime_nsec: metadata.mtime_nsec(),
+            size: metadata.size(),
+        })
+    }
+}
+
+/// Everything the daemon keeps between requests
+struct Daemon {
+    /// Manifest checked for changes before each request, None to never reload
+    manifest_path: Option<PathBuf>,
+    loaded: Mutex<Arc<Loaded>>,
+    /// Held while applying, so concurrent requests switch one after another
+    apply_lock: Mutex<()>,
+    /// Connections that asked to be sent every applied change
+    subscribers: Mutex<Vec<UnixStream>>,
+}
+
+impl Daemon {
+    fn load() -> Result<Self> {
+        let manifest_path = Config::manifest_path()?;
+        let loaded = Loaded::load(&manifest_path)?;
+        Ok(Self::new(loaded, Some(manifest_path)))
+    }
+
+    fn new(loaded: Loaded, manifest_path: Option<PathBuf>) -> Self {
+        Self {
+            manifest_path,
+            loaded: Mutex::new(Arc::new(loaded)),
+            apply_lock: Mutex::new(()),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The loaded manifest, loading it again first if the file changed
+    ///
+    /// A manifest that fails to load is reported and the previous one kept.
+    fn loaded(&self) -> Arc<Loaded> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(path) = &self.manifest_path
+            && ManifestStamp::of(path) != loaded.stamp
+        {
+            match Loaded::load(path) {
+                Ok(reloaded) => {
+                    info!("Manifest changed, reloaded {}", path.display());
+                    *loaded = Arc::new(reloaded);
+                }
+                Err(e) => warn!(
+                    "Manifest changed but failed to load, keeping the old one: {}",
+                    e
+                ),
+            }
+        }
+        Arc::clone(&loaded)
+    }
+
+    /// Render every variant of the current theme, so switching variants doesn't wait on templates
+    fn warm_cache(&self) {
+        let loaded = self.loaded();
+        let Some(cache) = &loaded.cache else {
+            return;
+        };
+        let state = match State::load() {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Not warming template cache: {}", e);
+                return;
+            }
+        };
+        let Some(theme_info) = theme::get_theme(&loaded.themes, &state.current_theme) else {
+            return;
+        };
+
+        for variant in &theme_info.variants {
+            if let Err(e) =
+                cache.get_or_render(&state.current_scheme, &theme_info.name, &variant.name)
+            {
+                warn!(
+                    "Failed to render {}-{}: {}",
+                    theme_info.name, variant.name, e
+                );
+            }
+        }
+    }
+
+    /// Accept connections until the listener fails, each on its own thread
+    fn serve(self: Arc<Self>, listener: UnixListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let daemon = Arc::clone(&self);
+            thread::spawn(move || {
+                if let Err(e) = daemon.handle_connection(stream) {
+                    debug!("Connection closed: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Answer requests on one connection until the client disconnects
+    fn handle_connection(&self, stream: UnixStream) -> Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        loop {
+            let request = match protocol::read_message::<Request>(&mut reader) {
+                Ok(Some(request)) => request,
+                Ok(None) => return Ok(()),
+                Err(VogixError::JsonParse(e)) => {
+                    let message = format!("invalid request: {}", e);
+                    protocol::write_message(&stream, &Response::Error { message })?;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            debug!("Received {:?}", request);
+
+            let subscribe = request == Request::Subscribe;
+            protocol::write_message(&stream, &self.handle(request))?;
+            if subscribe {
+                let subscriber = stream.try_clone()?;
+                subscriber.set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT))?;
+                self.subscribers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(subscriber);
+            }
+        }
+    }
+
+    fn handle(&self, request: Request) -> Response {
+        let loaded = self.loaded();
+        let result = match request {
+            Request::Apply { request, quiet } => self.apply(&loaded, &request, quiet),
+            Request::Status => State::load().map(|state| {
+                Response::Status(StatusReport::new(
+                    state,
+                    &loaded.themes,
+                    loaded.cache.as_ref(),
+                ))
+            }),
+            Request::List { scheme } => Ok(Response::Themes {
+                themes: match scheme {
+                    Some(scheme) => theme::filter_by_scheme(&loaded.themes, &scheme),
+                    None => loaded.themes.clone(),
+                },
+            }),
+            Request::Subscribe => Ok(Response::Subscribed),
+        };
+        result.unwrap_or_else(|e| Response::Error {
+            message: e.to_string(),
+        })
+    }
+
+    fn apply(&self, loaded: &Loaded, request: &ThemeRequest, quiet: bool) -> Result<Response> {
+        let _guard = self
+            .apply_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Also keeps out CLI commands that change the state directly
+        let state_lock = State::lock()?;
+
+        let previous = State::load()?;
+        let state = resolve_request(&previous, &loaded.themes, request)?;
+        if !is_change(&previous, &state) {
+            return Ok(Response::Unchanged);
+        }
+
+        log_changes(&previous, &state);
+        let reload_result = apply_state_with_cache(
+            &state,
+            &previous,
+            &loaded.config,
+            &loaded.themes,
+            loaded.cache.as_ref(),
+            AppliedBy::Daemon,
+            quiet,
+        )?;
+        record_change(&state_lock, &previous, &state);
+
+        let response = Response::Applied {
+            previous,
+            state,
+            success_count: reload_result.success_count,
+            total_count: reload_result.total_count,
+            failed_apps: reload_result.failed_apps.into_iter().collect(),
+        };
+        self.broadcast(&response);
+        Ok(response)
+    }
+
+    /// Send `event` to every subscriber, dropping those that can't be written to
+    fn broadcast(&self, event: &Response) {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|subscriber| protocol::write_message(subscriber, event).is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+//! Messages exchanged over the daemon socket.
+//!
+//! Every message is a single line of JSON. Requests are tagged by `command`,
+//! replies by `response`:
+//!
+//! ```text
+//! → {"command":"apply","theme":"nord","variant":"dark"}
+//! ← {"response":"applied","previous":{...},"state":{...},...}
+//! ```
+
+use crate::cli::ThemeRequest;
+use crate::commands::status::StatusReport;
+use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
+use crate::state::State;
+use crate::theme::types::ThemeInfo;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// File name of the socket inside $XDG_RUNTIME_DIR
+pub const SOCKET_NAME: &str = "vogix.sock";
+
+/// Path of the daemon socket, or None if XDG_RUNTIME_DIR is not set
+pub fn socket_path() -> Option<PathBuf> {
+    dirs::runtime_dir().map(|dir| dir.join(SOCKET_NAME))
+}
+
+/// A request to the daemon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    /// Change scheme, theme and/or variant, like the -s, -t and -v flags
+    Apply {
+        #[serde(flatten)]
+        request: ThemeRequest,
+        /// Suppress the daemon's success output for this change, like -q
+        #[serde(default)]
+        quiet: bool,
+    },
+    /// Report the current state
+    Status,
+    /// List themes, optionally only those of one scheme
+    List {
+        #[serde(default)]
+        scheme: Option<Scheme>,
+    },
+    /// Keep the connection open and receive every `applied` message
+    Subscribe,
+}
+
+/// A reply from the daemon, or an event pushed to subscribers
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum Response {
+    /// A change was applied (also sent to subscribers)
+    Applied {
+        previous: State,
+        state: State,
+        success_count: usize,
+        total_count: usize,
+        /// Reload error per app that failed to reload
+        failed_apps: BTreeMap<String, String>,
+    },
+    /// The request resolved to the current selection
+    Unchanged,
+    Status(StatusReport),
+    Themes {
+        themes: Vec<ThemeInfo>,
+    },
+    Subscribed,
+    Error {
+        message: String,
+    },
+}
+
+impl Response {
+    /// Convert an error reply, or a reply that doesn't answer the request, into an error
+    pub fn into_error(self) -> VogixError {
+        match self {
+            Response::Error { message } => VogixError::Daemon(message),
+            other => VogixError::Daemon(format!("unexpected response: {:?}", other)),
+        }
+    }
+}
+
+/// Write `message` as one line of JSON
+pub fn write_message<T: Serialize>(mut writer: impl Write, message: &T) -> Result<()> {
+    let mut line = serde_json::to_string(message).map_err(VogixError::JsonSerialize)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read the next message, or None once the other side has closed the connection
+pub fn read_message<T: DeserializeOwned>(reader: &mut impl BufRead) -> Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(VogixError::JsonParse)
+}
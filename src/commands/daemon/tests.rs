@@ -0,0 +1,283 @@
+//! Tests for daemon module
+
+use super::client::send_request_to;
+use super::*;
+use crate::scheme::Scheme;
+use crate::theme::types::VariantInfo;
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn theme_info(name: &str, scheme: Scheme) -> ThemeInfo {
+    ThemeInfo {
+        name: name.to_string(),
+        scheme,
+        variants: vec![VariantInfo {
+            name: "dark".to_string(),
+            polarity: "dark".to_string(),
+            order: 0,
+        }],
+    }
+}
+
+fn state(theme: &str) -> State {
+    State {
+        current_scheme: Scheme::Base16,
+        current_theme: theme.to_string(),
+        current_variant: "dark".to_string(),
+        last_applied: None,
+        provenance: None,
+    }
+}
+
+/// Start a daemon with two themes on a socket in `temp_dir`
+fn start_daemon(temp_dir: &TempDir) -> (Arc<Daemon>, PathBuf) {
+    let path = temp_dir.path().join(protocol::SOCKET_NAME);
+    let listener = bind(&path).unwrap();
+    let loaded = Loaded {
+        config: Config::default(),
+        themes: vec![
+            theme_info("gruvbox", Scheme::Base16),
+            theme_info("catppuccin", Scheme::Base24),
+        ],
+        cache: None,
+        stamp: None,
+    };
+    let daemon = Arc::new(Daemon::new(loaded, None));
+    let serving = Arc::clone(&daemon);
+    thread::spawn(move || serving.serve(listener));
+    (daemon, path)
+}
+
+#[test]
+fn test_request_wire_format() {
+    let request: Request =
+        serde_json::from_str(r#"{"command":"apply","theme":"nord","variant":"dark"}"#).unwrap();
+    assert_eq!(
+        request,
+        Request::Apply {
+            request: ThemeRequest {
+                scheme: None,
+                theme: Some("nord".to_string()),
+                variant: Some("dark".to_string()),
+            },
+            quiet: false,
+        }
+    );
+
+    let request: Request =
+        serde_json::from_str(r#"{"command":"apply","variant":"light","quiet":true}"#).unwrap();
+    assert!(matches!(request, Request::Apply { quiet: true, .. }));
+
+    let request: Request = serde_json::from_str(r#"{"command":"list"}"#).unwrap();
+    assert_eq!(request, Request::List { scheme: None });
+
+    assert_eq!(
+        serde_json::to_string(&Request::List {
+            scheme: Some(Scheme::Base24)
+        })
+        .unwrap(),
+        r#"{"command":"list","scheme":"base24"}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&Request::Subscribe).unwrap(),
+        r#"{"command":"subscribe"}"#
+    );
+}
+
+#[test]
+fn test_status_response_round_trip() {
+    let response = Response::Status(StatusReport {
+        state: state("gruvbox"),
+        polarity: Some("dark".to_string()),
+        cache_path: None,
+    });
+    let json = serde_json::to_string(&response).unwrap();
+    assert!(json.starts_with(r#"{"response":"status","current_scheme":"base16""#));
+
+    let Response::Status(report) = serde_json::from_str(&json).unwrap() else {
+        panic!("expected a status response");
+    };
+    assert_eq!(report.state.current_theme, "gruvbox");
+    assert_eq!(report.polarity.as_deref(), Some("dark"));
+}
+
+#[test]
+fn test_applied_response_lists_failed_apps_by_name() {
+    let response = Response::Applied {
+        previous: state("gruvbox"),
+        state: state("nord"),
+        success_count: 1,
+        total_count: 2,
+        failed_apps: BTreeMap::from([("kitty".to_string(), "timed out".to_string())]),
+    };
+    let value = serde_json::to_value(&response).unwrap();
+    assert_eq!(value["response"], "applied");
+    assert_eq!(value["failed_apps"]["kitty"], "timed out");
+}
+
+#[test]
+fn test_into_error() {
+    let error = Response::Error {
+        message: "invalid theme: Theme 'nope' not found".to_string(),
+    }
+    .into_error();
+    assert_eq!(
+        error.to_string(),
+        "daemon error: invalid theme: Theme 'nope' not found"
+    );
+    assert!(
+        Response::Subscribed
+            .into_error()
+            .to_string()
+            .contains("unexpected response")
+    );
+}
+
+#[test]
+fn test_no_daemon_falls_back() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(protocol::SOCKET_NAME);
+    assert!(send_request_to(&path, &Request::Status).unwrap().is_none());
+}
+
+#[test]
+fn test_list_request() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_daemon, path) = start_daemon(&temp_dir);
+
+    let response = send_request_to(
+        &path,
+        &Request::List {
+            scheme: Some(Scheme::Base24),
+        },
+    )
+    .unwrap()
+    .unwrap();
+    let Response::Themes { themes } = response else {
+        panic!("expected a theme list, got {:?}", response);
+    };
+    let names: Vec<_> = themes.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["catppuccin"]);
+}
+
+#[test]
+fn test_invalid_request_keeps_connection_open() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_daemon, path) = start_daemon(&temp_dir);
+
+    let mut stream = UnixStream::connect(&path).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    writeln!(stream, r#"{{"command":"reboot"}}"#).unwrap();
+    let response: Response = protocol::read_message(&mut reader).unwrap().unwrap();
+    let Response::Error { message } = response else {
+        panic!("expected an error, got {:?}", response);
+    };
+    assert!(message.starts_with("invalid request"), "{}", message);
+
+    writeln!(stream, r#"{{"command":"list"}}"#).unwrap();
+    let response: Response = protocol::read_message(&mut reader).unwrap().unwrap();
+    assert!(matches!(response, Response::Themes { themes } if themes.len() == 2));
+}
+
+#[test]
+fn test_subscribers_receive_broadcasts() {
+    let temp_dir = TempDir::new().unwrap();
+    let (daemon, path) = start_daemon(&temp_dir);
+
+    let stream = UnixStream::connect(&path).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    protocol::write_message(&stream, &Request::Subscribe).unwrap();
+    let response: Response = protocol::read_message(&mut reader).unwrap().unwrap();
+    assert!(matches!(response, Response::Subscribed));
+
+    // Registration happens right after the reply is written
+    while daemon.subscribers.lock().unwrap().is_empty() {
+        thread::yield_now();
+    }
+    daemon.broadcast(&Response::Unchanged);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "{\"response\":\"unchanged\"}\n");
+}
+
+#[test]
+fn test_bind_refuses_running_daemon() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_daemon, path) = start_daemon(&temp_dir);
+
+    let error = bind(&path).unwrap_err();
+    assert!(error.to_string().contains("already running"));
+}
+
+#[test]
+fn test_bind_replaces_stale_socket() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(protocol::SOCKET_NAME);
+    drop(bind(&path).unwrap());
+    assert!(path.exists());
+
+    let _listener = bind(&path).unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_manifest_stamp_sees_replaced_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(&path, "a").unwrap();
+    let stamp = ManifestStamp::of(&path);
+    assert!(stamp.is_some());
+    assert_eq!(ManifestStamp::of(&path), stamp);
+
+    // Same size and mtime, like a new store file swapped in by home-manager
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    let replacement = temp_dir.path().join("config.toml.new");
+    fs::write(&replacement, "b").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&replacement)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    fs::rename(&replacement, &path).unwrap();
+    assert_ne!(ManifestStamp::of(&path), stamp);
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(ManifestStamp::of(&path), None);
+}
+
+#[test]
+fn test_manifest_change_is_reloaded() {
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("config.toml");
+    let manifest = "[themes.gruvbox]\nscheme = \"base16\"\nvariants = [\"dark\"]\n";
+    fs::write(&manifest_path, manifest).unwrap();
+    let daemon = Daemon::new(
+        Loaded::load(&manifest_path).unwrap(),
+        Some(manifest_path.clone()),
+    );
+    let names = |daemon: &Daemon| match daemon.handle(Request::List { scheme: None }) {
+        Response::Themes { themes } => themes.into_iter().map(|t| t.name).collect::<Vec<_>>(),
+        response => panic!("expected a theme list, got {:?}", response),
+    };
+    assert_eq!(names(&daemon), vec!["gruvbox"]);
+
+    let replacement = temp_dir.path().join("config.toml.new");
+    let extended = format!(
+        "{}[themes.nord]\nscheme = \"base16\"\nvariants = [\"dark\"]\n",
+        manifest
+    );
+    fs::write(&replacement, extended).unwrap();
+    fs::rename(&replacement, &manifest_path).unwrap();
+    let mut reloaded = names(&daemon);
+    reloaded.sort();
+    assert_eq!(reloaded, vec!["gruvbox", "nord"]);
+
+    // A broken manifest keeps the last good one
+    fs::write(&manifest_path, "[themes").unwrap();
+    assert_eq!(names(&daemon).len(), 2);
+}
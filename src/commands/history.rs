@@ -0,0 +1,90 @@
+//! History commands - list, undo and redo theme changes.
+
+use crate::cli::OutputFormat;
+use crate::config::Config;
+use crate::errors::Result;
+use crate::history::History;
+use crate::state::{AppliedBy, State, StateLock};
+use crate::theme;
+use log::{info, warn};
+
+use super::output;
+use super::theme_change::apply_state;
+
+/// Handle the `history` command - list recorded theme changes, oldest first
+pub fn handle_history(format: OutputFormat) -> Result<()> {
+    let history = History::load()?;
+
+    if let Some(rendered) = output::render(&history, format)? {
+        print!("{}", rendered);
+        return Ok(());
+    }
+
+    if history.entries.is_empty() {
+        info!("No theme changes recorded yet");
+        return Ok(());
+    }
+
+    for (index, entry) in history.entries.iter().enumerate() {
+        let marker = if index == history.position { "*" } else { " " };
+        println!(
+            "{} {:>2}  {}  {:<8} {}-{}",
+            marker, index, entry.applied_at, entry.scheme, entry.theme, entry.variant
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle the `undo` command - reapply the previous history entry
+pub fn handle_undo(quiet: bool) -> Result<()> {
+    step(quiet, true)
+}
+
+/// Handle the `redo` command - reapply the entry undone last
+pub fn handle_redo(quiet: bool) -> Result<()> {
+    step(quiet, false)
+}
+
+/// Move the history cursor and apply the entry it lands on
+///
+/// The cursor is only saved once the entry has been applied, so a failed
+/// switch can be retried.
+fn step(quiet: bool, back: bool) -> Result<()> {
+    let config = Config::load()?;
+    let themes = theme::discover_themes()?;
+    // Held across the history read and write too, like record_change
+    let _lock = State::lock()?;
+    let mut state = State::load()?;
+    let mut history = History::load()?;
+
+    let entry = if back { history.undo() } else { history.redo() };
+    let Some(entry) = entry.cloned() else {
+        info!("Nothing to {}", if back { "undo" } else { "redo" });
+        return Ok(());
+    };
+
+    info!(
+        "{}-{} → {}-{}",
+        state.current_theme, state.current_variant, entry.theme, entry.variant
+    );
+    let previous = state.clone();
+    entry.apply_to(&mut state);
+    apply_state(&state, &previous, &config, &themes, AppliedBy::Cli, quiet)?;
+    history.save()
+}
+
+/// Record a successful change in the history log
+///
+/// Takes the state lock the change was applied under, so concurrent switches
+/// can't lose each other's entries. Failing to write history never fails the
+/// switch itself.
+pub fn record_change(_lock: &StateLock, previous: &State, applied: &State) {
+    let result = History::load().and_then(|mut history| {
+        history.record(previous, applied);
+        history.save()
+    });
+    if let Err(e) = result {
+        warn!("Failed to record theme history: {}", e);
+    }
+}
@@ -1,15 +1,47 @@
 //! List command - show available themes and schemes.
 
+use crate::cli::OutputFormat;
 use crate::errors::Result;
 use crate::scheme::Scheme;
 use crate::theme;
 use crate::theme::types::ThemeInfo;
 use log::info;
+use serde::Serialize;
 use std::borrow::Cow;
 
+use super::daemon::{self, Request, Response};
+use super::output;
+
+/// Structured `list` output: every theme with its variants
+#[derive(Serialize)]
+struct ThemeListing<'a> {
+    themes: &'a [ThemeInfo],
+}
+
 /// Handle the `list` command - display themes and schemes
-pub fn handle_list(filter_scheme: Option<&Scheme>, show_variants: bool) -> Result<()> {
-    let all_themes = theme::discover_themes()?;
+///
+/// Uses the daemon's theme list when one is running.
+pub fn handle_list(
+    filter_scheme: Option<&Scheme>,
+    show_variants: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let all_themes = match daemon::send_request(&Request::List { scheme: None })? {
+        Some(Response::Themes { themes }) => themes,
+        Some(other) => return Err(other.into_error()),
+        None => theme::discover_themes()?,
+    };
+
+    if format != OutputFormat::Text {
+        let themes = match filter_scheme {
+            Some(scheme) => theme::filter_by_scheme(&all_themes, scheme),
+            None => all_themes,
+        };
+        if let Some(rendered) = output::render(&ThemeListing { themes: &themes }, format)? {
+            print!("{}", rendered);
+        }
+        return Ok(());
+    }
 
     if all_themes.is_empty() {
         info!("No themes found");
@@ -75,3 +107,55 @@ pub fn handle_list(filter_scheme: Option<&Scheme>, show_variants: bool) -> Resul
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::types::VariantInfo;
+
+    fn themes() -> Vec<ThemeInfo> {
+        let variant = |name: &str, polarity: &str, order| VariantInfo {
+            name: name.to_string(),
+            polarity: polarity.to_string(),
+            order,
+        };
+        vec![
+            ThemeInfo {
+                name: "catppuccin".to_string(),
+                scheme: Scheme::Base24,
+                variants: vec![variant("latte", "light", 0), variant("mocha", "dark", 1)],
+            },
+            ThemeInfo {
+                name: "dracula".to_string(),
+                scheme: Scheme::Base16,
+                variants: vec![variant("dark", "dark", 0)],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_list_json_snapshot() {
+        let themes = themes();
+        let rendered = output::render(&ThemeListing { themes: &themes }, OutputFormat::Json);
+        insta::assert_snapshot!(rendered.unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_list_toml_snapshot() {
+        let themes = themes();
+        let rendered = output::render(&ThemeListing { themes: &themes }, OutputFormat::Toml);
+        insta::assert_snapshot!(rendered.unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_list_empty_json() {
+        let rendered = output::render(&ThemeListing { themes: &[] }, OutputFormat::Json);
+        assert_eq!(rendered.unwrap().unwrap(), "{\n  \"themes\": []\n}\n");
+    }
+
+    #[test]
+    fn test_list_text_is_not_structured() {
+        let rendered = output::render(&ThemeListing { themes: &[] }, OutputFormat::Text);
+        assert!(rendered.unwrap().is_none());
+    }
+}
@@ -3,21 +3,43 @@
 //! This module provides handlers for each CLI command:
 //! - `list` - Show available themes and schemes
 //! - `status` - Display current theme state
+//! - `show` - Print a theme variant's palette
+//! - `pick` - Choose a theme interactively with a live preview
+//! - `history`, `undo`, `redo` - Browse and step through past theme changes
+//! - `auto` - Switch polarity on a time or sunrise/sunset schedule
+//! - `daemon` - Serve theme changes, status and theme lists over a Unix socket
+//! - `watch` - Stream theme changes as JSON lines
 //! - `refresh` - Reapply current theme without changes
 //! - `cache` - Manage template cache
+//! - `config` - Validate the manifest
 //! - `completions` - Generate shell completions
 //! - `theme_change` - Handle -t, -v, -s flags
 
+mod auto;
 mod cache;
 mod completions;
+mod config;
+mod daemon;
+mod history;
 mod list;
+mod output;
+mod pick;
 mod refresh;
+mod show;
 mod status;
 mod theme_change;
+mod watch;
 
-pub use cache::handle_cache_clean;
+pub use auto::handle_auto;
+pub use cache::{handle_cache_clean, handle_cache_verify};
 pub use completions::handle_completions;
+pub use config::handle_config_check;
+pub use daemon::handle_daemon;
+pub use history::{handle_history, handle_redo, handle_undo};
 pub use list::handle_list;
+pub use pick::handle_pick;
 pub use refresh::handle_refresh;
+pub use show::handle_show;
 pub use status::handle_status;
 pub use theme_change::handle_theme_change;
+pub use watch::handle_watch;
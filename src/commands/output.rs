@@ -0,0 +1,23 @@
+//! Structured output for `--format json|toml`.
+
+use crate::cli::OutputFormat;
+use crate::errors::{Result, VogixError};
+use serde::Serialize;
+
+/// Serialize `value` for a structured format
+///
+/// Returns `None` for `OutputFormat::Text`, leaving the command to print its
+/// own human-readable output. The result always ends with a newline.
+pub fn render<T: Serialize>(value: &T, format: OutputFormat) -> Result<Option<String>> {
+    let rendered = match format {
+        OutputFormat::Text => return Ok(None),
+        OutputFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(value).map_err(VogixError::JsonSerialize)?;
+            json.push('\n');
+            json
+        }
+        OutputFormat::Toml => toml::to_string_pretty(value).map_err(VogixError::TomlSerialize)?,
+    };
+    Ok(Some(rendered))
+}
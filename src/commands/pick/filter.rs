@@ -0,0 +1,35 @@
+//! Fuzzy filtering of the theme list
+
+use crate::scheme::Scheme;
+use crate::theme::types::ThemeInfo;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// Indices of the themes matching `query` within `scheme`, best match first
+///
+/// An empty query keeps every theme of the scheme in name order.
+pub fn fuzzy_filter(themes: &[ThemeInfo], scheme: Option<Scheme>, query: &str) -> Vec<usize> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+
+    let mut scored: Vec<(i64, usize)> = themes
+        .iter()
+        .enumerate()
+        .filter(|(_, theme)| scheme.is_none_or(|s| theme.scheme == s))
+        .filter_map(|(idx, theme)| {
+            if query.is_empty() {
+                Some((0, idx))
+            } else {
+                matcher
+                    .fuzzy_match(&theme.name, query)
+                    .map(|score| (score, idx))
+            }
+        })
+        .collect();
+
+    scored.sort_by(|(score_a, idx_a), (score_b, idx_b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| themes[*idx_a].name.cmp(&themes[*idx_b].name))
+    });
+    scored.into_iter().map(|(_, idx)| idx).collect()
+}
@@ -0,0 +1,354 @@
+//! Pick command - interactive theme picker with live preview.
+//!
+//! Shows a fuzzy-filterable theme list next to a palette swatch and sample
+//! text for the highlighted variant, then applies the choice through the
+//! same pipeline as the theme flags.
+//!
+//! # Module Structure
+//! - `filter`: fuzzy matching of theme names
+//! - `preview`: swatch and sample rendering with truecolor escapes
+
+mod filter;
+mod preview;
+#[cfg(test)]
+mod tests;
+
+use crate::cache::theme_variant_path;
+use crate::color::Color;
+use crate::config::Config;
+use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
+use crate::state::{AppliedBy, State};
+use crate::theme::{self, types::ThemeInfo};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, execute, queue, terminal};
+use log::info;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::history::record_change;
+use super::theme_change::apply_state;
+
+/// Width of the theme list column
+const LIST_WIDTH: u16 = 28;
+
+/// Scheme filter order when cycling with Tab (None = all schemes)
+const SCHEME_CYCLE: [Option<Scheme>; 5] = [
+    None,
+    Some(Scheme::Vogix16),
+    Some(Scheme::Base16),
+    Some(Scheme::Base24),
+    Some(Scheme::Ansi16),
+];
+
+/// What the picker should do after a key press
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    /// Apply (theme, variant)
+    Apply(String, String),
+    Cancel,
+}
+
+/// Picker state, independent of the terminal
+struct Picker<'a> {
+    themes: &'a [ThemeInfo],
+    scheme: Option<Scheme>,
+    query: String,
+    /// Indices into `themes`, best match first
+    matches: Vec<usize>,
+    /// Highlighted row in `matches`
+    selected: usize,
+    /// Highlighted variant, as an index into the theme's variants by order
+    variant: usize,
+}
+
+impl<'a> Picker<'a> {
+    /// Start with the current theme and variant highlighted
+    fn new(themes: &'a [ThemeInfo], scheme: Option<Scheme>, state: &State) -> Self {
+        let mut picker = Self {
+            themes,
+            scheme,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            variant: 0,
+        };
+        picker.refilter();
+
+        if let Some(row) = picker
+            .matches
+            .iter()
+            .position(|&idx| themes[idx].name == state.current_theme)
+        {
+            picker.selected = row;
+            picker.variant = themes[picker.matches[row]]
+                .variants_by_order()
+                .iter()
+                .position(|v| v.name == state.current_variant)
+                .unwrap_or(0);
+        }
+        picker
+    }
+
+    /// Theme and variant under the cursor
+    fn highlighted(&self) -> Option<(&'a ThemeInfo, &'a str)> {
+        let theme = &self.themes[*self.matches.get(self.selected)?];
+        let variants = theme.variants_by_order();
+        let variant = variants.get(self.variant).or(variants.first())?;
+        Some((theme, variant.name.as_str()))
+    }
+
+    fn refilter(&mut self) {
+        self.matches = filter::fuzzy_filter(self.themes, self.scheme, &self.query);
+        self.selected = 0;
+        self.variant = 0;
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1).min(self.matches.len() - 1)
+        } else {
+            self.selected.saturating_sub(1)
+        };
+        self.variant = 0;
+    }
+
+    fn cycle_variant(&mut self, forward: bool) {
+        let Some((theme, _)) = self.highlighted() else {
+            return;
+        };
+        let count = theme.variants.len();
+        if count > 0 {
+            self.variant = if forward {
+                (self.variant + 1) % count
+            } else {
+                (self.variant + count - 1) % count
+            };
+        }
+    }
+
+    fn cycle_scheme(&mut self) {
+        let current = SCHEME_CYCLE
+            .iter()
+            .position(|s| *s == self.scheme)
+            .unwrap_or(0);
+        self.scheme = SCHEME_CYCLE[(current + 1) % SCHEME_CYCLE.len()];
+        self.refilter();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Action::Cancel,
+            KeyCode::Char('c') if ctrl => return Action::Cancel,
+            KeyCode::Enter => {
+                return match self.highlighted() {
+                    Some((theme, variant)) => {
+                        Action::Apply(theme.name.clone(), variant.to_string())
+                    }
+                    None => Action::Continue,
+                };
+            }
+            KeyCode::Up => self.move_selection(false),
+            KeyCode::Char('p') if ctrl => self.move_selection(false),
+            KeyCode::Down => self.move_selection(true),
+            KeyCode::Char('n') if ctrl => self.move_selection(true),
+            KeyCode::Left => self.cycle_variant(false),
+            KeyCode::Right => self.cycle_variant(true),
+            KeyCode::Tab => self.cycle_scheme(),
+            KeyCode::Backspace if self.query.pop().is_some() => self.refilter(),
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+}
+
+/// Handle the `pick` command - choose a theme interactively and apply it
+pub fn handle_pick(scheme: Option<Scheme>, quiet: bool) -> Result<()> {
+    let config = Config::load()?;
+    let themes = theme::discover_themes()?;
+
+    if themes.is_empty() {
+        info!("No themes found");
+        info!("Add themes to your NixOS/home-manager configuration");
+        return Ok(());
+    }
+
+    let mut picker = Picker::new(&themes, scheme, &State::load()?);
+    let action = run_terminal(&mut picker, &config)?;
+
+    let Action::Apply(theme_name, variant) = action else {
+        return Ok(());
+    };
+    let theme_info = theme::get_theme(&themes, &theme_name)
+        .ok_or_else(|| VogixError::InvalidTheme(format!("Theme '{}' not found", theme_name)))?;
+
+    // The picker can stay open for a while, so the state is only locked and
+    // read again once a choice is made, keeping changes made in the meantime
+    let lock = State::lock()?;
+    let mut state = State::load()?;
+    if state.current_scheme == theme_info.scheme
+        && state.current_theme == theme_name
+        && state.current_variant == variant
+    {
+        info!("No changes to apply");
+        return Ok(());
+    }
+
+    let previous = state.clone();
+    state.current_scheme = theme_info.scheme;
+    state.current_theme = theme_name;
+    state.current_variant = variant;
+    apply_state(&state, &previous, &config, &themes, AppliedBy::Cli, quiet)?;
+    record_change(&lock, &previous, &state);
+    Ok(())
+}
+
+/// Run the picker on the alternate screen, restoring the terminal afterwards
+fn run_terminal(picker: &mut Picker<'_>, config: &Config) -> Result<Action> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = event_loop(&mut out, picker, config);
+
+    let _ = execute!(out, cursor::Show, terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+    result
+}
+
+fn event_loop(out: &mut impl Write, picker: &mut Picker<'_>, config: &Config) -> Result<Action> {
+    // Loaded colors per "theme-variant", or the error to show instead
+    let mut previews: HashMap<String, std::result::Result<HashMap<String, Color>, String>> =
+        HashMap::new();
+
+    loop {
+        draw(out, picker, config, &mut previews)?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match picker.handle_key(key) {
+                Action::Continue => {}
+                action => return Ok(action),
+            }
+        }
+    }
+}
+
+fn draw(
+    out: &mut impl Write,
+    picker: &Picker<'_>,
+    config: &Config,
+    previews: &mut HashMap<String, std::result::Result<HashMap<String, Color>, String>>,
+) -> Result<()> {
+    let (cols, rows) = terminal::size()?;
+    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+
+    let scheme_label = picker
+        .scheme
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "all".to_string());
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        Print(format!("[{}] > {}", scheme_label, picker.query))
+    )?;
+
+    // Theme list, scrolled so the selection stays visible
+    let list_rows = usize::from(rows.saturating_sub(3));
+    let first = picker.selected.saturating_sub(list_rows.saturating_sub(1));
+    for (row, &idx) in picker
+        .matches
+        .iter()
+        .skip(first)
+        .take(list_rows)
+        .enumerate()
+    {
+        let name: String = picker.themes[idx]
+            .name
+            .chars()
+            .take(usize::from(LIST_WIDTH) - 2)
+            .collect();
+        queue!(out, cursor::MoveTo(0, row as u16 + 2))?;
+        if first + row == picker.selected {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reverse),
+                Print(format!("> {}", name)),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(out, Print(format!("  {}", name)))?;
+        }
+    }
+
+    if let Some((theme_info, variant)) = picker.highlighted() {
+        let variants: Vec<String> = theme_info
+            .variants_by_order()
+            .iter()
+            .map(|v| {
+                if v.name == variant {
+                    format!("[{}]", v.name)
+                } else {
+                    v.name.clone()
+                }
+            })
+            .collect();
+        queue!(
+            out,
+            cursor::MoveTo(LIST_WIDTH, 2),
+            Print(format!(
+                "{} ({})  {}",
+                theme_info.name,
+                theme_info.scheme,
+                variants.join(" ")
+            ))
+        )?;
+
+        let key = format!("{}-{}", theme_info.name, variant);
+        let colors = previews
+            .entry(key)
+            .or_insert_with(|| load_preview(config, theme_info, variant));
+        let width = usize::from(cols.saturating_sub(LIST_WIDTH + 1)).min(64);
+        let lines = match colors {
+            Ok(colors) => preview::render(colors, theme_info.scheme, width),
+            Err(message) => vec![message.clone()],
+        };
+        for (row, line) in lines.iter().enumerate() {
+            queue!(out, cursor::MoveTo(LIST_WIDTH, row as u16 + 4), Print(line))?;
+        }
+    }
+
+    queue!(
+        out,
+        cursor::MoveTo(0, rows.saturating_sub(1)),
+        Print("↑/↓ theme  ←/→ variant  Tab scheme  Enter apply  Esc cancel")
+    )?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Load the colors of a variant from its theme source file
+fn load_preview(
+    config: &Config,
+    theme_info: &ThemeInfo,
+    variant: &str,
+) -> std::result::Result<HashMap<String, Color>, String> {
+    let sources = config
+        .theme_sources
+        .as_ref()
+        .ok_or_else(|| "no preview: [theme_sources] not configured".to_string())?;
+    let path = theme_variant_path(sources, &theme_info.scheme, &theme_info.name, variant);
+    theme::load_theme_colors(&path, theme_info.scheme, sources.fill_missing)
+        .map_err(|e| format!("no preview: {}", e))
+}
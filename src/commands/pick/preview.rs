@@ -0,0 +1,138 @@
+//! Palette swatches and sample text for the highlighted variant
+//!
+//! Lines are plain strings with truecolor escapes so they can be drawn at any
+//! position and checked in tests without a terminal.
+
+use crate::color;
+use crate::scheme::Scheme;
+use crate::theme::palette_keys;
+use crossterm::style::{Color, Stylize};
+use std::collections::HashMap;
+
+/// Which loaded color plays each role in the sample text
+struct Roles {
+    background: &'static str,
+    foreground: &'static str,
+    comment: &'static str,
+    keyword: &'static str,
+    string: &'static str,
+    function: &'static str,
+    number: &'static str,
+    kind: &'static str,
+    error: &'static str,
+}
+
+/// Base16-style keys; vogix16 maps the same slots to semantic roles
+const BASE_ROLES: Roles = Roles {
+    background: "base00",
+    foreground: "base05",
+    comment: "base03",
+    keyword: "base0E",
+    string: "base0B",
+    function: "base0D",
+    number: "base09",
+    kind: "base0A",
+    error: "base08",
+};
+
+/// vogix16 uses base08 for success and base0B for danger
+const VOGIX16_ROLES: Roles = Roles {
+    string: "base08",
+    error: "base0B",
+    ..BASE_ROLES
+};
+
+const ANSI16_ROLES: Roles = Roles {
+    background: "background",
+    foreground: "foreground",
+    comment: "color08",
+    keyword: "color05",
+    string: "color02",
+    function: "color04",
+    number: "color03",
+    kind: "color06",
+    error: "color01",
+};
+
+/// Swatches per row
+const SWATCHES_PER_ROW: usize = 8;
+
+/// Render swatch rows and a code/terminal sample, each line `width` columns wide
+pub fn render(colors: &HashMap<String, color::Color>, scheme: Scheme, width: usize) -> Vec<String> {
+    let roles = match scheme {
+        Scheme::Vogix16 => &VOGIX16_ROLES,
+        Scheme::Base16 | Scheme::Base24 => &BASE_ROLES,
+        Scheme::Ansi16 => &ANSI16_ROLES,
+    };
+    let color = |key: &str| {
+        colors
+            .get(key)
+            .map(|&color::Color { r, g, b, .. }| Color::Rgb { r, g, b })
+            .unwrap_or(Color::Reset)
+    };
+
+    let mut lines = Vec::new();
+
+    for row in palette_keys(colors).chunks(SWATCHES_PER_ROW) {
+        let line: String = row
+            .iter()
+            .map(|key| format!("{} ", "    ".on(color(key))))
+            .collect();
+        lines.push(line);
+    }
+    lines.push(String::new());
+
+    let background = color(roles.background);
+    let sample: [&[(&str, &str)]; 9] = [
+        &[(roles.comment, "// switch the active theme")],
+        &[
+            (roles.keyword, "fn "),
+            (roles.function, "apply"),
+            (roles.foreground, "(name: "),
+            (roles.kind, "&str"),
+            (roles.foreground, ") -> "),
+            (roles.kind, "Result"),
+            (roles.foreground, " {"),
+        ],
+        &[
+            (roles.keyword, "    let "),
+            (roles.foreground, "count = "),
+            (roles.number, "42"),
+            (roles.foreground, ";"),
+        ],
+        &[
+            (roles.function, "    println!"),
+            (roles.foreground, "("),
+            (roles.string, "\"applied {}\""),
+            (roles.foreground, ", name);"),
+        ],
+        &[
+            (roles.error, "    Err"),
+            (roles.foreground, "("),
+            (roles.string, "\"not found\""),
+            (roles.foreground, ")"),
+        ],
+        &[(roles.foreground, "}")],
+        &[],
+        &[(roles.function, "$ "), (roles.foreground, "vogix refresh")],
+        &[
+            (roles.string, "✓ "),
+            (roles.foreground, "Reloaded 4 applications"),
+        ],
+    ];
+
+    for segments in sample {
+        let mut line = String::new();
+        let mut used = 0;
+        for (role, text) in segments {
+            let text: String = text.chars().take(width.saturating_sub(used)).collect();
+            used += text.chars().count();
+            line.push_str(&text.with(color(role)).on(background).to_string());
+        }
+        let padding = " ".repeat(width.saturating_sub(used));
+        line.push_str(&padding.on(background).to_string());
+        lines.push(line);
+    }
+
+    lines
+}
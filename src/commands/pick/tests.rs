@@ -0,0 +1,195 @@
+//! Tests for pick module
+
+use super::*;
+use crate::theme::types::VariantInfo;
+
+fn theme_info(name: &str, scheme: Scheme, variants: &[(&str, &str)]) -> ThemeInfo {
+    ThemeInfo {
+        name: name.to_string(),
+        scheme,
+        variants: variants
+            .iter()
+            .enumerate()
+            .map(|(order, (name, polarity))| VariantInfo {
+                name: name.to_string(),
+                polarity: polarity.to_string(),
+                order: order as u32,
+            })
+            .collect(),
+    }
+}
+
+fn themes() -> Vec<ThemeInfo> {
+    vec![
+        theme_info(
+            "aikido",
+            Scheme::Vogix16,
+            &[("day", "light"), ("night", "dark")],
+        ),
+        theme_info(
+            "catppuccin",
+            Scheme::Base24,
+            &[("latte", "light"), ("frappe", "dark"), ("mocha", "dark")],
+        ),
+        theme_info("dracula", Scheme::Base16, &[("dark", "dark")]),
+        theme_info(
+            "gruvbox",
+            Scheme::Base16,
+            &[("light", "light"), ("dark", "dark")],
+        ),
+    ]
+}
+
+fn state(theme: &str, variant: &str) -> State {
+    State {
+        current_theme: theme.to_string(),
+        current_variant: variant.to_string(),
+        ..State::default()
+    }
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_query(picker: &mut Picker<'_>, query: &str) {
+    for c in query.chars() {
+        assert_eq!(picker.handle_key(key(KeyCode::Char(c))), Action::Continue);
+    }
+}
+
+fn names(themes: &[ThemeInfo], indices: &[usize]) -> Vec<String> {
+    indices.iter().map(|&i| themes[i].name.clone()).collect()
+}
+
+#[test]
+fn test_fuzzy_filter_empty_query_keeps_scheme() {
+    let themes = themes();
+    let all = filter::fuzzy_filter(&themes, None, "");
+    assert_eq!(all.len(), 4);
+
+    let base16 = filter::fuzzy_filter(&themes, Some(Scheme::Base16), "");
+    assert_eq!(names(&themes, &base16), vec!["dracula", "gruvbox"]);
+}
+
+#[test]
+fn test_fuzzy_filter_matches_subsequence() {
+    let themes = themes();
+    let matches = filter::fuzzy_filter(&themes, None, "cpn");
+    assert_eq!(names(&themes, &matches), vec!["catppuccin"]);
+
+    assert!(filter::fuzzy_filter(&themes, None, "zzz").is_empty());
+    assert!(filter::fuzzy_filter(&themes, Some(Scheme::Vogix16), "cat").is_empty());
+}
+
+#[test]
+fn test_fuzzy_filter_ignores_case() {
+    let themes = themes();
+    let matches = filter::fuzzy_filter(&themes, None, "DRA");
+    assert_eq!(names(&themes, &matches), vec!["dracula"]);
+}
+
+#[test]
+fn test_picker_starts_on_current_theme() {
+    let themes = themes();
+    let picker = Picker::new(&themes, None, &state("catppuccin", "mocha"));
+
+    let (theme, variant) = picker.highlighted().unwrap();
+    assert_eq!(theme.name, "catppuccin");
+    assert_eq!(variant, "mocha");
+}
+
+#[test]
+fn test_picker_filter_and_apply() {
+    let themes = themes();
+    let mut picker = Picker::new(&themes, None, &state("aikido", "night"));
+
+    type_query(&mut picker, "gruv");
+    assert_eq!(picker.matches.len(), 1);
+
+    picker.handle_key(key(KeyCode::Right));
+    assert_eq!(
+        picker.handle_key(key(KeyCode::Enter)),
+        Action::Apply("gruvbox".to_string(), "dark".to_string())
+    );
+}
+
+#[test]
+fn test_picker_backspace_restores_matches() {
+    let themes = themes();
+    let mut picker = Picker::new(&themes, None, &state("aikido", "night"));
+
+    type_query(&mut picker, "zz");
+    assert!(picker.highlighted().is_none());
+    assert_eq!(picker.handle_key(key(KeyCode::Enter)), Action::Continue);
+
+    picker.handle_key(key(KeyCode::Backspace));
+    picker.handle_key(key(KeyCode::Backspace));
+    assert_eq!(picker.matches.len(), 4);
+}
+
+#[test]
+fn test_picker_navigation_stays_in_bounds() {
+    let themes = themes();
+    let mut picker = Picker::new(&themes, None, &state("aikido", "night"));
+
+    picker.handle_key(key(KeyCode::Up));
+    assert_eq!(picker.selected, 0);
+
+    for _ in 0..10 {
+        picker.handle_key(key(KeyCode::Down));
+    }
+    assert_eq!(picker.selected, 3);
+
+    // Variants wrap around
+    picker.handle_key(key(KeyCode::Left));
+    assert_eq!(picker.highlighted().unwrap().1, "dark");
+}
+
+#[test]
+fn test_picker_tab_cycles_scheme() {
+    let themes = themes();
+    let mut picker = Picker::new(&themes, None, &state("aikido", "night"));
+
+    picker.handle_key(key(KeyCode::Tab));
+    assert_eq!(picker.scheme, Some(Scheme::Vogix16));
+    assert_eq!(names(&themes, &picker.matches), vec!["aikido"]);
+
+    for _ in 0..4 {
+        picker.handle_key(key(KeyCode::Tab));
+    }
+    assert_eq!(picker.scheme, None);
+}
+
+#[test]
+fn test_picker_cancel() {
+    let themes = themes();
+    let mut picker = Picker::new(&themes, None, &state("aikido", "night"));
+
+    assert_eq!(picker.handle_key(key(KeyCode::Esc)), Action::Cancel);
+    assert_eq!(
+        picker.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Action::Cancel
+    );
+    assert!(picker.query.is_empty());
+}
+
+#[test]
+fn test_render_preview_uses_theme_colors() {
+    let colors: HashMap<String, Color> = (0..16)
+        .map(|i| (format!("color{:02}", i), Color::rgb(0, 0, i)))
+        .chain([
+            ("background".to_string(), Color::rgb(0x10, 0x10, 0x10)),
+            ("foreground".to_string(), Color::rgb(0xe0, 0xe0, 0xe0)),
+        ])
+        .collect();
+
+    let lines = preview::render(&colors, Scheme::Ansi16, 40);
+
+    // Two swatch rows, a spacer, then the sample
+    assert!(lines[0].contains("48;2;0;0;0m"));
+    assert!(lines[1].contains("48;2;0;0;15m"));
+    assert!(lines[2].is_empty());
+    assert!(lines[3].contains("48;2;16;16;16m"));
+    assert!(lines.iter().any(|l| l.contains("38;2;224;224;224m")));
+}
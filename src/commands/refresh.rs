@@ -3,33 +3,61 @@
 use crate::cache::ThemeCache;
 use crate::config::Config;
 use crate::errors::Result;
+use crate::hooks::Hooks;
 use crate::reload::ReloadDispatcher;
-use crate::state::State;
+use crate::state::{AppliedBy, Provenance, State};
 use crate::symlink::SymlinkManager;
 use crate::theme;
 use log::{debug, warn};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Handle the `refresh` command - reapply current theme state without changes
 pub fn handle_refresh(quiet: bool) -> Result<()> {
-    let state = State::load()?;
     let config = Config::load()?;
+    let themes = theme::discover_themes()?;
+    let _lock = State::lock()?;
+    let state = State::load()?;
 
-    theme::verify_theme_variant_exists(&state.current_theme, &state.current_variant)?;
+    let theme_dir =
+        theme::verify_theme_variant_exists(&state.current_theme, &state.current_variant)?;
 
     // Render templates to cache if configured
-    if let Some(cache_path) = maybe_render_templates(&config, &state)? {
-        debug!(
-            "Using template-rendered configs from: {}",
-            cache_path.display()
-        );
+    let cache = template_cache(&config)?;
+    let cache_path = cache
+        .as_ref()
+        .map(|cache| render_templates(cache, &state))
+        .transpose()?;
+
+    let hooks = Hooks::for_switch(
+        &config,
+        &state,
+        &themes,
+        cache_path.as_deref().unwrap_or(&theme_dir),
+    );
+    if let Some(hooks) = &hooks {
+        hooks.run_pre_apply()?;
     }
 
-    let symlink_manager = SymlinkManager::new();
-    symlink_manager.update_current_symlink(&state.current_theme, &state.current_variant)?;
+    switch_symlinks(&state, cache_path.as_deref())?;
+
+    // Record the refresh, with the templates it rendered
+    State {
+        provenance: Some(Provenance {
+            applied_by: AppliedBy::Refresh,
+            templates_hash: cache.map(|cache| cache.templates_hash().to_string()),
+            cache_path,
+        }),
+        ..state
+    }
+    .save()?;
 
     let reload_dispatcher = ReloadDispatcher::new();
     let reload_result = reload_dispatcher.reload_apps(&config, quiet);
+    reload_result.log_details();
+
+    if let Some(hooks) = &hooks {
+        hooks.run_post_apply();
+    }
 
     if reload_result.has_failures() {
         warn!(
@@ -44,26 +72,39 @@ pub fn handle_refresh(quiet: bool) -> Result<()> {
     Ok(())
 }
 
-/// Render templates to cache and update state symlink if template-based rendering is configured
-/// Returns Ok(Some(path)) if templates were rendered, Ok(None) if not configured
-pub fn maybe_render_templates(config: &Config, state: &State) -> Result<Option<PathBuf>> {
-    // Only render if templates are configured
+/// Template cache for the manifest, or None if template rendering is not configured
+pub fn template_cache(config: &Config) -> Result<Option<ThemeCache>> {
     if config.templates.is_none() {
         debug!("Template rendering not configured, using pre-generated configs");
         return Ok(None);
     }
+    ThemeCache::from_config(config).map(Some)
+}
 
-    let cache = ThemeCache::from_config(config)?;
+/// Render the state's variant through `cache`, returning the rendered directory
+pub fn render_templates(cache: &ThemeCache, state: &State) -> Result<PathBuf> {
     let cache_path = cache.get_or_render(
         &state.current_scheme,
         &state.current_theme,
         &state.current_variant,
     )?;
+    debug!("Rendered templates to: {}", cache_path.display());
+    Ok(cache_path)
+}
 
-    // Update the state directory symlink to point to cached configs
+/// Point the symlinks at the state's variant
+///
+/// `cache_path` is the rendered directory when template rendering is configured.
+pub fn switch_symlinks(state: &State, cache_path: Option<&Path>) -> Result<()> {
     let symlink_manager = SymlinkManager::new();
-    symlink_manager.update_state_current_symlink(&cache_path)?;
-
-    debug!("Rendered templates to: {}", cache_path.display());
-    Ok(Some(cache_path))
+    if let Some(cache_path) = cache_path {
+        symlink_manager.update_state_current_symlink(cache_path)?;
+        debug!(
+            "Using template-rendered configs from: {}",
+            cache_path.display()
+        );
+    }
+    symlink_manager.update_current_symlink(&state.current_theme, &state.current_variant)?;
+    debug!("Updated current symlink");
+    Ok(())
 }
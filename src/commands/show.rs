@@ -0,0 +1,196 @@
+//! Show command - print a theme variant's palette with color swatches.
+
+use crate::cli::OutputFormat;
+use crate::color;
+use crate::config::Config;
+use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
+use crate::state::State;
+use crate::theme;
+use crossterm::style::{Color, Stylize};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::output;
+use super::theme_change::resolve_variant;
+
+/// Structured `show` output: the loaded color map of one variant
+#[derive(Serialize)]
+struct ColorDump<'a> {
+    theme: &'a str,
+    variant: &'a str,
+    scheme: Scheme,
+    colors: BTreeMap<&'a str, color::Color>,
+}
+
+/// Handle the `show` command - print every color of a theme variant
+///
+/// Without `-v`, shows the active variant when `theme` is the current theme,
+/// otherwise the theme's default dark variant.
+pub fn handle_show(theme_name: &str, variant: Option<&str>, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let themes = theme::discover_themes()?;
+    let theme_info = theme::get_theme(&themes, theme_name)
+        .ok_or_else(|| VogixError::InvalidTheme(format!("Theme '{}' not found", theme_name)))?;
+
+    let variant = match variant {
+        Some(requested) => resolve_variant(&theme_info, requested)?,
+        None => {
+            let state = State::load()?;
+            if state.current_theme == theme_name {
+                state.current_variant
+            } else {
+                theme_info
+                    .default_variant_for_polarity("dark")
+                    .map(|v| v.name.clone())
+                    .ok_or_else(|| {
+                        VogixError::InvalidTheme(format!("Theme '{}' has no variants", theme_name))
+                    })?
+            }
+        }
+    };
+
+    let colors = theme::load_variant_colors(&config, &theme_info, &variant)?;
+
+    let dump = ColorDump {
+        theme: &theme_info.name,
+        variant: &variant,
+        scheme: theme_info.scheme,
+        colors: colors
+            .iter()
+            .map(|(key, color)| (key.as_str(), *color))
+            .collect(),
+    };
+    if let Some(rendered) = output::render(&dump, format)? {
+        print!("{}", rendered);
+        return Ok(());
+    }
+
+    println!("{}-{} ({})", theme_info.name, variant, theme_info.scheme);
+    println!();
+
+    let truecolor = std::env::var("COLORTERM")
+        .is_ok_and(|value| matches!(value.as_str(), "truecolor" | "24bit"));
+    let keys = display_order(&colors);
+    let key_width = keys.iter().map(|key| key.len()).max().unwrap_or(0);
+    for key in keys {
+        println!("{}", format_row(key, colors[key], key_width, truecolor));
+    }
+
+    Ok(())
+}
+
+/// Palette keys in palette order, followed by named keys alphabetically
+fn display_order(colors: &HashMap<String, color::Color>) -> Vec<&str> {
+    let mut keys = theme::palette_keys(colors);
+    let mut named: Vec<&str> = colors
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !keys.contains(key))
+        .collect();
+    named.sort_unstable();
+    keys.extend(named);
+    keys
+}
+
+/// One palette line: swatch, key, hex, RGB, HSL and relative luminance
+///
+/// Uses a 24-bit swatch when `truecolor` is set, otherwise the nearest
+/// 256-color palette entry.
+fn format_row(key: &str, value: color::Color, key_width: usize, truecolor: bool) -> String {
+    let rgb = value.to_rgb();
+    let (r, g, b) = rgb;
+    let swatch_color = if truecolor {
+        Color::Rgb { r, g, b }
+    } else {
+        Color::AnsiValue(color::nearest_ansi256(rgb))
+    };
+    let (h, s, l) = color::to_hsl(rgb);
+
+    format!(
+        "{} {:<key_width$}  {:<7}  rgb({:>3}, {:>3}, {:>3})  hsl({:>3.0}, {:>3.0}%, {:>3.0}%)  luminance {:.3}",
+        "    ".on(swatch_color),
+        key,
+        value.to_string(),
+        r,
+        g,
+        b,
+        h,
+        s,
+        l,
+        color::relative_luminance(rgb)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(keys: &[&str]) -> HashMap<String, color::Color> {
+        keys.iter()
+            .map(|k| (k.to_string(), color::Color::rgb(0, 0, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_display_order_palette_first() {
+        let colors = colors(&["warning", "base01", "background", "base00", "base0A"]);
+        assert_eq!(
+            display_order(&colors),
+            vec!["base00", "base01", "base0A", "background", "warning"]
+        );
+    }
+
+    #[test]
+    fn test_show_json_snapshot() {
+        let dump = ColorDump {
+            theme: "gruvbox",
+            variant: "dark",
+            scheme: Scheme::Base16,
+            colors: BTreeMap::from([
+                ("base00", color::Color::rgb(0x28, 0x28, 0x28)),
+                ("base01", color::Color::rgb(0x3c, 0x38, 0x36)),
+                ("base08", color::Color::rgb(0xfb, 0x49, 0x34)),
+            ]),
+        };
+        insta::assert_snapshot!(output::render(&dump, OutputFormat::Json).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_show_toml_snapshot() {
+        let dump = ColorDump {
+            theme: "aikido",
+            variant: "night",
+            scheme: Scheme::Vogix16,
+            colors: BTreeMap::from([
+                ("background", color::Color::rgb(0x26, 0x26, 0x26)),
+                ("base00", color::Color::rgb(0x26, 0x26, 0x26)),
+                ("danger", color::Color::rgb(0xd7, 0x50, 0x3c)),
+            ]),
+        };
+        insta::assert_snapshot!(output::render(&dump, OutputFormat::Toml).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_format_row_truecolor() {
+        let row = format_row("base08", color::Color::rgb(255, 0, 0), 10, true);
+        assert!(row.starts_with("\x1b[48;2;255;0;0m    "));
+        assert!(row.contains(
+            "base08      #ff0000  rgb(255,   0,   0)  hsl(  0, 100%,  50%)  luminance 0.213"
+        ));
+    }
+
+    #[test]
+    fn test_format_row_ansi256_fallback() {
+        let row = format_row("base08", color::Color::rgb(255, 0, 0), 6, false);
+        assert!(row.starts_with("\x1b[48;5;196m    "));
+        assert!(!row.contains("48;2;"));
+    }
+
+    #[test]
+    fn test_format_row_keeps_alpha() {
+        let translucent = color::Color::parse("#ff000080").unwrap();
+        let row = format_row("selection_bg", translucent, 12, true);
+        assert!(row.contains("selection_bg  #ff000080  rgb(255,   0,   0)"));
+    }
+}
@@ -1,20 +1,218 @@
 //! Status command - show current theme state.
 
+use crate::cache::ThemeCache;
+use crate::cli::OutputFormat;
+use crate::config::Config;
 use crate::errors::Result;
 use crate::state::State;
+use crate::theme::{self, types::ThemeInfo};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::daemon::{self, Request, Response};
+use super::output;
+
+/// Structured `status` output: the saved state plus values resolved from it
+///
+/// Also the daemon's reply to a `status` request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusReport {
+    #[serde(flatten)]
+    pub state: State,
+    /// Polarity of the current variant, if the theme is in the manifest
+    pub polarity: Option<String>,
+    /// Template cache directory for the current variant, if templates are configured
+    pub cache_path: Option<PathBuf>,
+}
+
+impl StatusReport {
+    /// Resolve the polarity and cache path of `state`
+    pub fn new(state: State, themes: &[ThemeInfo], cache: Option<&ThemeCache>) -> Self {
+        let polarity = theme::get_theme(themes, &state.current_theme).and_then(|t| {
+            t.variants
+                .into_iter()
+                .find(|v| v.name == state.current_variant)
+                .map(|v| v.polarity)
+        });
+        let cache_path = cache.map(|cache| {
+            cache.variant_cache_path(
+                &state.current_scheme,
+                &state.current_theme,
+                &state.current_variant,
+            )
+        });
+        StatusReport {
+            state,
+            polarity,
+            cache_path,
+        }
+    }
+}
 
 /// Handle the `status` command - display current theme/variant/scheme
-pub fn handle_status() -> Result<()> {
-    let state = State::load()?;
-    state.save()?;
+///
+/// Asks the daemon when one is running, otherwise reads the state file.
+/// Never writes the state.
+pub fn handle_status(format: OutputFormat) -> Result<()> {
+    let report = match daemon::send_request(&Request::Status)? {
+        Some(Response::Status(report)) => report,
+        Some(other) => return Err(other.into_error()),
+        None => local_report()?,
+    };
 
+    if let Some(rendered) = output::render(&report, format)? {
+        print!("{}", rendered);
+        return Ok(());
+    }
+
+    let state = &report.state;
     println!("scheme:  {}", state.current_scheme);
     println!("theme:   {}", state.current_theme);
     println!("variant: {}", state.current_variant);
 
     if let Some(ref last_applied) = state.last_applied {
-        println!("applied: {}", last_applied);
+        match &state.provenance {
+            Some(provenance) => println!("applied: {} by {}", last_applied, provenance.applied_by),
+            None => println!("applied: {}", last_applied),
+        }
+    }
+    let provenance = state.provenance.as_ref();
+    if let Some(templates_hash) = provenance.and_then(|p| p.templates_hash.as_ref()) {
+        println!("hash:    {}", templates_hash);
+    }
+    // Where the configs were rendered to, or for older states where they would be
+    if let Some(cache_path) = provenance
+        .and_then(|p| p.cache_path.as_ref())
+        .or(report.cache_path.as_ref())
+    {
+        println!("cache:   {}", cache_path.display());
     }
 
     Ok(())
 }
+
+/// Build the report without a daemon
+fn local_report() -> Result<StatusReport> {
+    let state = State::load()?;
+    let themes = theme::discover_themes().unwrap_or_default();
+    let cache = Config::load()
+        .ok()
+        .and_then(|config| ThemeCache::from_config(&config).ok());
+    Ok(StatusReport::new(state, &themes, cache.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::Scheme;
+    use crate::state::{AppliedBy, Provenance};
+
+    fn state() -> State {
+        State {
+            current_scheme: Scheme::Base16,
+            current_theme: "gruvbox".to_string(),
+            current_variant: "dark".to_string(),
+            last_applied: Some("2026-01-01T00:00:00+00:00".to_string()),
+            provenance: Some(Provenance {
+                applied_by: AppliedBy::Daemon,
+                templates_hash: Some("abc123".to_string()),
+                cache_path: Some(PathBuf::from(
+                    "/home/user/.cache/vogix/themes/abc123/base16/gruvbox/dark",
+                )),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_status_json_snapshot() {
+        let report = StatusReport {
+            state: state(),
+            polarity: Some("dark".to_string()),
+            cache_path: Some(PathBuf::from(
+                "/home/user/.cache/vogix/themes/abc123/base16/gruvbox/dark",
+            )),
+        };
+        insta::assert_snapshot!(
+            output::render(&report, OutputFormat::Json)
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_status_toml_snapshot() {
+        let report = StatusReport {
+            state: state(),
+            polarity: Some("dark".to_string()),
+            cache_path: Some(PathBuf::from(
+                "/home/user/.cache/vogix/themes/abc123/base16/gruvbox/dark",
+            )),
+        };
+        insta::assert_snapshot!(
+            output::render(&report, OutputFormat::Toml)
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_status_json_unresolved_fields_are_null() {
+        let state = State {
+            last_applied: None,
+            provenance: None,
+            ..state()
+        };
+        let report = StatusReport {
+            state,
+            polarity: None,
+            cache_path: None,
+        };
+        insta::assert_snapshot!(
+            output::render(&report, OutputFormat::Json)
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_status_toml_omits_unresolved_fields() {
+        let state = State {
+            last_applied: None,
+            provenance: None,
+            ..state()
+        };
+        let report = StatusReport {
+            state,
+            polarity: None,
+            cache_path: None,
+        };
+        let rendered = output::render(&report, OutputFormat::Toml)
+            .unwrap()
+            .unwrap();
+        assert!(!rendered.contains("polarity"));
+        assert!(!rendered.contains("provenance"));
+        assert!(!rendered.contains("cache_path"));
+        assert!(rendered.contains("current_theme = \"gruvbox\""));
+    }
+
+    #[test]
+    fn test_status_without_provenance_keeps_cache_path() {
+        let state = State {
+            provenance: None,
+            ..state()
+        };
+        let report = StatusReport {
+            state,
+            polarity: Some("dark".to_string()),
+            cache_path: Some(PathBuf::from(
+                "/home/user/.cache/vogix/themes/abc123/base16/gruvbox/dark",
+            )),
+        };
+        let value = serde_json::to_value(&report).unwrap();
+        assert!(value["provenance"].is_null());
+        assert_eq!(
+            value["cache_path"],
+            "/home/user/.cache/vogix/themes/abc123/base16/gruvbox/dark"
+        );
+    }
+}
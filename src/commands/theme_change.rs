@@ -1,149 +1,298 @@
 //! Theme change command - handle -t, -v, -s flags.
 
-use crate::cli::Cli;
+use crate::cache::ThemeCache;
+use crate::cli::{Cli, ThemeRequest};
 use crate::config::Config;
 use crate::errors::{Result, VogixError};
-use crate::reload::ReloadDispatcher;
-use crate::state::State;
+use crate::hooks::Hooks;
+use crate::reload::{self, ReloadDispatcher, ReloadResult};
+use crate::state::{AppliedBy, Provenance, State};
 use crate::symlink::SymlinkManager;
-use crate::theme;
+use crate::theme::{self, types::ThemeInfo};
 use log::{debug, info, warn};
 
-use super::refresh::maybe_render_templates;
+use super::daemon::{self, Request, Response};
+use super::history::record_change;
+use super::refresh::{render_templates, switch_symlinks, template_cache};
 
 /// Handle theme/variant/scheme changes via flags (-t, -v, -s)
+///
+/// Handed to the daemon when one is running, otherwise applied directly.
 pub fn handle_theme_change(cli: &Cli) -> Result<()> {
-    let mut state = State::load()?;
+    let request = cli.theme_request();
+
+    let daemon_request = Request::Apply {
+        request: request.clone(),
+        quiet: cli.quiet,
+    };
+    if let Some(response) = daemon::send_request(&daemon_request)? {
+        return match response {
+            Response::Applied {
+                previous,
+                state,
+                success_count,
+                total_count,
+                failed_apps,
+            } => {
+                log_changes(&previous, &state);
+                // The daemon's own output goes to its log, so report the reload here
+                let failed_apps: Vec<_> = failed_apps.into_iter().collect();
+                reload::print_summary(success_count, total_count, &failed_apps, cli.quiet);
+                log_applied(&state, success_count, total_count, failed_apps.len());
+                Ok(())
+            }
+            Response::Unchanged => {
+                info!("No changes to apply");
+                Ok(())
+            }
+            other => Err(other.into_error()),
+        };
+    }
+
     let config = Config::load()?;
+    let themes = theme::discover_themes()?;
 
-    let old_scheme = state.current_scheme;
-    let old_theme = state.current_theme.clone();
-    let old_variant = state.current_variant.clone();
+    // Held until the change is applied and recorded, so concurrent
+    // invocations switch one after another instead of interleaving
+    let lock = State::lock()?;
+    let previous = State::load()?;
+    let state = resolve_request(&previous, &themes, &request)?;
+
+    // Check if anything changed
+    if !is_change(&previous, &state) {
+        info!("No changes to apply");
+        return Ok(());
+    }
+
+    log_changes(&previous, &state);
+    apply_state(
+        &state,
+        &previous,
+        &config,
+        &themes,
+        AppliedBy::Cli,
+        cli.quiet,
+    )?;
+    record_change(&lock, &previous, &state);
+    Ok(())
+}
+
+/// Work out the selection a theme change request leads to from `current`
+pub fn resolve_request(
+    current: &State,
+    themes: &[ThemeInfo],
+    request: &ThemeRequest,
+) -> Result<State> {
+    let mut state = current.clone();
 
     // Update scheme if provided
-    if let Some(scheme) = cli.scheme {
+    if let Some(scheme) = request.scheme {
         state.current_scheme = scheme;
     }
 
     // Track if theme changed (we'll need to resolve variant for new theme)
-    let theme_changed = cli.theme.is_some() && cli.theme.as_ref() != Some(&state.current_theme);
+    let theme_changed =
+        request.theme.is_some() && request.theme.as_ref() != Some(&state.current_theme);
 
     // Update theme if provided
-    if let Some(ref theme) = cli.theme {
+    if let Some(ref theme) = request.theme {
         state.current_theme = theme.clone();
     }
 
+    let theme_info = theme::get_theme(themes, &state.current_theme).ok_or_else(|| {
+        VogixError::InvalidTheme(format!("Theme '{}' not found", state.current_theme))
+    })?;
+
     // Update variant if provided, OR resolve default variant if theme changed
-    if let Some(ref variant) = cli.variant {
-        if cli.is_variant_navigation() {
-            state.current_variant = navigate_variant(&state, variant)?;
+    if let Some(ref variant) = request.variant {
+        if request.is_variant_navigation() {
+            // Use the theme's luminance-based navigation
+            state.current_variant = theme_info.navigate(&state.current_variant, variant)?;
         } else {
             // Resolve variant: could be an exact variant name OR a polarity request (dark/light)
-            state.current_variant =
-                resolve_variant(&state.current_theme, variant, &state.current_variant)?;
+            state.current_variant = resolve_variant(&theme_info, variant)?;
         }
     } else if theme_changed {
         // Theme changed but no variant specified - find appropriate variant for current polarity
-        let themes = theme::discover_themes()?;
-        if let Some(new_theme) = theme::get_theme(&themes, &state.current_theme) {
-            // Get the polarity of the old variant to maintain dark/light preference
-            let current_polarity = theme::get_theme(&themes, &old_theme)
-                .and_then(|t| {
-                    t.variants
-                        .iter()
-                        .find(|v| v.name == old_variant)
-                        .map(|v| v.polarity.clone())
-                })
-                .unwrap_or_else(|| "dark".to_string());
+        // Get the polarity of the old variant to maintain dark/light preference
+        let current_polarity = theme::get_theme(themes, &current.current_theme)
+            .and_then(|t| {
+                t.variants
+                    .iter()
+                    .find(|v| v.name == current.current_variant)
+                    .map(|v| v.polarity.clone())
+            })
+            .unwrap_or_else(|| "dark".to_string());
 
-            // Find the default variant for this polarity in the new theme
-            if let Some(default_var) = new_theme.default_variant_for_polarity(&current_polarity) {
-                state.current_variant = default_var.name.clone();
-            }
+        // Find the default variant for this polarity in the new theme
+        if let Some(default_var) = theme_info.default_variant_for_polarity(&current_polarity) {
+            state.current_variant = default_var.name.clone();
         }
     }
 
-    // Check if anything changed
-    if state.current_scheme == old_scheme
-        && state.current_theme == old_theme
-        && state.current_variant == old_variant
-    {
-        info!("No changes to apply");
-        return Ok(());
-    }
+    Ok(state)
+}
+
+/// Whether `state` selects a different scheme, theme or variant than `previous`
+pub fn is_change(previous: &State, state: &State) -> bool {
+    state.current_scheme != previous.current_scheme
+        || state.current_theme != previous.current_theme
+        || state.current_variant != previous.current_variant
+}
 
-    // Log changes
-    if state.current_scheme != old_scheme {
-        info!("scheme: {} → {}", old_scheme, state.current_scheme);
+/// Log each part of the selection that changed
+pub fn log_changes(previous: &State, state: &State) {
+    if state.current_scheme != previous.current_scheme {
+        info!(
+            "scheme: {} → {}",
+            previous.current_scheme, state.current_scheme
+        );
     }
-    if state.current_theme != old_theme {
-        info!("theme: {} → {}", old_theme, state.current_theme);
+    if state.current_theme != previous.current_theme {
+        info!(
+            "theme: {} → {}",
+            previous.current_theme, state.current_theme
+        );
     }
-    if state.current_variant != old_variant {
-        info!("variant: {} → {}", old_variant, state.current_variant);
+    if state.current_variant != previous.current_variant {
+        info!(
+            "variant: {} → {}",
+            previous.current_variant, state.current_variant
+        );
     }
+}
 
+/// Apply a theme selection: render templates, switch symlinks, save state and reload apps
+///
+/// If an app marked `critical` fails to reload, the previous symlink target and
+/// state are restored and apps are reloaded again.
+///
+/// Shared by the theme flags (-t, -v, -s), `vogix pick` and `vogix undo`/`redo`.
+/// Callers record history themselves, since undo/redo move through it instead.
+/// `previous` is the state the caller loaded under the state lock.
+pub fn apply_state(
+    state: &State,
+    previous: &State,
+    config: &Config,
+    themes: &[ThemeInfo],
+    applied_by: AppliedBy,
+    quiet: bool,
+) -> Result<ReloadResult> {
+    let cache = template_cache(config)?;
+    apply_state_with_cache(
+        state,
+        previous,
+        config,
+        themes,
+        cache.as_ref(),
+        applied_by,
+        quiet,
+    )
+}
+
+/// Like [`apply_state`], rendering templates through an already loaded cache
+///
+/// Used by the daemon, which keeps its cache between requests.
+pub fn apply_state_with_cache(
+    state: &State,
+    previous: &State,
+    config: &Config,
+    themes: &[ThemeInfo],
+    cache: Option<&ThemeCache>,
+    applied_by: AppliedBy,
+    quiet: bool,
+) -> Result<ReloadResult> {
     // Verify theme-variant exists
-    theme::verify_theme_variant_exists(&state.current_theme, &state.current_variant)?;
+    let theme_dir =
+        theme::verify_theme_variant_exists(&state.current_theme, &state.current_variant)?;
     debug!("Verified theme-variant exists");
 
     // Render templates to cache if configured (for template-based architecture)
-    if let Some(cache_path) = maybe_render_templates(&config, &state)? {
-        debug!(
-            "Using template-rendered configs from: {}",
-            cache_path.display()
-        );
+    let cache_path = cache
+        .map(|cache| render_templates(cache, state))
+        .transpose()?;
+
+    // Nothing has been switched yet, so a failing pre-apply hook aborts cleanly
+    let hooks = Hooks::for_switch(
+        config,
+        state,
+        themes,
+        cache_path.as_deref().unwrap_or(&theme_dir),
+    );
+    if let Some(hooks) = &hooks {
+        hooks.run_pre_apply()?;
     }
 
-    // Update the 'current' symlink
+    // Remember the current selection, to roll back to if the switch fails
     let symlink_manager = SymlinkManager::new();
-    symlink_manager.update_current_symlink(&state.current_theme, &state.current_variant)?;
-    debug!("Updated current symlink");
+    let previous_target = symlink_manager.current_target()?;
+    let previous_state_file = State::snapshot()?;
 
-    // Save state
-    state.save()?;
+    let applied = State {
+        provenance: Some(Provenance {
+            applied_by,
+            templates_hash: cache.map(|cache| cache.templates_hash().to_string()),
+            cache_path: cache_path.clone(),
+        }),
+        ..state.clone()
+    };
+    let switched = switch_symlinks(state, cache_path.as_deref()).and_then(|()| applied.save());
+    if let Err(e) = switched {
+        symlink_manager.restore_current_symlink(previous_target.as_deref())?;
+        return Err(e);
+    }
     debug!("Saved state");
 
     // Reload applications
     let reload_dispatcher = ReloadDispatcher::new();
-    let reload_result = reload_dispatcher.reload_apps(&config, cli.quiet);
+    let reload_result = reload_dispatcher.reload_apps(config, quiet);
+    reload_result.log_details();
+
+    let critical_failures = reload_result.critical_failures(config);
+    if !critical_failures.is_empty() {
+        let message = format!(
+            "critical app(s) failed to reload: {}; rolled back to {}-{}",
+            critical_failures.join(", "),
+            previous.current_theme,
+            previous.current_variant
+        );
+        symlink_manager.restore_current_symlink(previous_target.as_deref())?;
+        State::restore(previous_state_file.as_deref())?;
+        reload_dispatcher.reload_apps(config, quiet).log_details();
+        return Err(VogixError::reload(message));
+    }
+
+    if let Some(hooks) = &hooks {
+        hooks.run_post_apply();
+    }
 
-    // Log appropriate message based on reload results
+    log_applied(
+        state,
+        reload_result.success_count,
+        reload_result.total_count,
+        reload_result.failed_apps.len(),
+    );
+    Ok(reload_result)
+}
+
+/// Log appropriate message based on reload results
+fn log_applied(state: &State, success_count: usize, total_count: usize, failed_count: usize) {
     let theme_variant = format!("{}-{}", state.current_theme, state.current_variant);
-    if reload_result.has_failures() {
+    if failed_count > 0 {
         warn!(
             "Applied: {} ({}/{} reloaded, {} failed)",
-            theme_variant,
-            reload_result.success_count,
-            reload_result.total_count,
-            reload_result.failed_apps.len()
+            theme_variant, success_count, total_count, failed_count
         );
     } else {
         info!("Applied: {}", theme_variant);
     }
-    Ok(())
-}
-
-/// Navigate to a darker or lighter variant based on luminance ordering
-fn navigate_variant(state: &State, direction: &str) -> Result<String> {
-    // Load themes and find the current one
-    let themes = theme::discover_themes()?;
-    let current_theme = theme::get_theme(&themes, &state.current_theme).ok_or_else(|| {
-        VogixError::InvalidTheme(format!("Theme '{}' not found", state.current_theme))
-    })?;
-
-    // Use the theme's luminance-based navigation
-    current_theme.navigate(&state.current_variant, direction)
 }
 
 /// Resolve a variant name: could be an exact variant name OR a polarity request (dark/light)
 /// For polarity requests, finds the default variant for that polarity in the theme.
 /// For single-variant themes, always returns the only variant (ignores polarity request).
-fn resolve_variant(theme_name: &str, requested: &str, _current_variant: &str) -> Result<String> {
-    let themes = theme::discover_themes()?;
-    let theme_info = theme::get_theme(&themes, theme_name)
-        .ok_or_else(|| VogixError::InvalidTheme(format!("Theme '{}' not found", theme_name)))?;
-
+pub fn resolve_variant(theme_info: &ThemeInfo, requested: &str) -> Result<String> {
+    let theme_name = &theme_info.name;
     let requested_lower = requested.to_lowercase();
 
     // First, check if requested is an exact variant name match (case-insensitive)
@@ -195,3 +344,145 @@ fn resolve_variant(theme_name: &str, requested: &str, _current_variant: &str) ->
         available.join(", ")
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::Scheme;
+    use crate::theme::types::VariantInfo;
+
+    fn theme_info(name: &str, scheme: Scheme, variants: &[(&str, &str)]) -> ThemeInfo {
+        ThemeInfo {
+            name: name.to_string(),
+            scheme,
+            variants: variants
+                .iter()
+                .enumerate()
+                .map(|(order, (name, polarity))| VariantInfo {
+                    name: name.to_string(),
+                    polarity: polarity.to_string(),
+                    order: order as u32,
+                })
+                .collect(),
+        }
+    }
+
+    fn themes() -> Vec<ThemeInfo> {
+        vec![
+            theme_info(
+                "catppuccin",
+                Scheme::Base24,
+                &[("latte", "light"), ("frappe", "dark"), ("mocha", "dark")],
+            ),
+            theme_info("dracula", Scheme::Base16, &[("dark", "dark")]),
+            theme_info(
+                "gruvbox",
+                Scheme::Base16,
+                &[("light", "light"), ("dark", "dark")],
+            ),
+        ]
+    }
+
+    fn state(theme: &str, variant: &str) -> State {
+        State {
+            current_scheme: Scheme::Base16,
+            current_theme: theme.to_string(),
+            current_variant: variant.to_string(),
+            last_applied: None,
+            provenance: None,
+        }
+    }
+
+    fn request(theme: Option<&str>, variant: Option<&str>) -> ThemeRequest {
+        ThemeRequest {
+            scheme: None,
+            theme: theme.map(String::from),
+            variant: variant.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_resolve_theme_change_keeps_polarity() {
+        let resolved = resolve_request(
+            &state("gruvbox", "light"),
+            &themes(),
+            &request(Some("catppuccin"), None),
+        )
+        .unwrap();
+        assert_eq!(resolved.current_theme, "catppuccin");
+        assert_eq!(resolved.current_variant, "latte");
+    }
+
+    #[test]
+    fn test_resolve_polarity_request() {
+        let resolved = resolve_request(
+            &state("catppuccin", "latte"),
+            &themes(),
+            &request(None, Some("dark")),
+        )
+        .unwrap();
+        assert_eq!(resolved.current_variant, "frappe");
+    }
+
+    #[test]
+    fn test_resolve_navigation() {
+        let resolved = resolve_request(
+            &state("catppuccin", "frappe"),
+            &themes(),
+            &request(None, Some("darker")),
+        )
+        .unwrap();
+        assert_eq!(resolved.current_variant, "mocha");
+
+        let at_edge = resolve_request(
+            &state("catppuccin", "mocha"),
+            &themes(),
+            &request(None, Some("darker")),
+        );
+        assert!(at_edge.is_err());
+    }
+
+    #[test]
+    fn test_resolve_unknown_theme() {
+        let err = resolve_request(
+            &state("gruvbox", "dark"),
+            &themes(),
+            &request(Some("nope"), None),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Theme 'nope' not found"));
+    }
+
+    #[test]
+    fn test_resolve_unknown_variant() {
+        let err = resolve_request(
+            &state("gruvbox", "dark"),
+            &themes(),
+            &request(None, Some("dusk")),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Available variants: light, dark"));
+    }
+
+    #[test]
+    fn test_resolve_single_variant_ignores_polarity() {
+        let themes = themes();
+        let dracula = theme::get_theme(&themes, "dracula").unwrap();
+        assert_eq!(resolve_variant(&dracula, "light").unwrap(), "dark");
+    }
+
+    #[test]
+    fn test_resolve_scheme_only() {
+        let resolved = resolve_request(
+            &state("gruvbox", "dark"),
+            &themes(),
+            &ThemeRequest {
+                scheme: Some(Scheme::Base24),
+                ..ThemeRequest::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(resolved.current_scheme, Scheme::Base24);
+        assert_eq!(resolved.current_theme, "gruvbox");
+    }
+}
@@ -0,0 +1,230 @@
+//! Watch command - stream theme changes as JSON lines.
+
+use crate::color::Color;
+use crate::config::Config;
+use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
+use crate::state::State;
+use crate::theme::{self, types::ThemeInfo};
+use inotify::{Inotify, WatchMask};
+use log::{debug, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Write};
+
+use super::theme_change::is_change;
+
+/// Files in the state directory that change when a theme is applied
+const WATCHED_FILES: [&str; 2] = ["state.toml", "current-theme"];
+
+/// One side of a change event
+#[derive(Debug, Serialize)]
+struct Selection {
+    scheme: Scheme,
+    theme: String,
+    variant: String,
+    /// None if the theme or variant is not in the manifest
+    polarity: Option<String>,
+}
+
+impl Selection {
+    fn new(state: &State, themes: &[ThemeInfo]) -> Self {
+        let polarity = theme::get_theme(themes, &state.current_theme).and_then(|t| {
+            t.variants
+                .into_iter()
+                .find(|v| v.name == state.current_variant)
+                .map(|v| v.polarity)
+        });
+        Selection {
+            scheme: state.current_scheme,
+            theme: state.current_theme.clone(),
+            variant: state.current_variant.clone(),
+            polarity,
+        }
+    }
+}
+
+/// A line of `watch` output
+#[derive(Debug, Serialize)]
+struct ChangeEvent {
+    /// None for the event describing the state at startup
+    old: Option<Selection>,
+    new: Selection,
+    /// Colors of the new variant, empty if they can't be loaded
+    colors: BTreeMap<String, Color>,
+}
+
+impl ChangeEvent {
+    /// Build the event for a change from `old` to `new`, loading the new variant's colors
+    fn new(old: Option<&State>, new: &State) -> Self {
+        let themes = theme::discover_themes().unwrap_or_default();
+        let colors = load_colors(&themes, new).unwrap_or_else(|e| {
+            warn!(
+                "No colors for {}-{}: {}",
+                new.current_theme, new.current_variant, e
+            );
+            BTreeMap::new()
+        });
+
+        ChangeEvent {
+            old: old.map(|state| Selection::new(state, &themes)),
+            new: Selection::new(new, &themes),
+            colors,
+        }
+    }
+}
+
+/// Handle the `watch` command - print a JSON line for the current theme and every change
+///
+/// Runs until stdout is closed.
+pub fn handle_watch() -> Result<()> {
+    let state_dir = State::state_dir()?;
+    fs::create_dir_all(&state_dir)?;
+
+    // Watch the directory rather than the files, so replaced files and
+    // re-created symlinks are still seen
+    let mut inotify = Inotify::init()?;
+    inotify.watches().add(
+        &state_dir,
+        WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE | WatchMask::DELETE,
+    )?;
+
+    let mut last = State::load()?;
+    if !emit(&ChangeEvent::new(None, &last))? {
+        return Ok(());
+    }
+
+    let mut buffer = [0; 4096];
+    loop {
+        let events = inotify.read_events_blocking(&mut buffer)?;
+        if !events
+            .into_iter()
+            .any(|event| event.name.is_some_and(is_watched))
+        {
+            continue;
+        }
+
+        let state = match State::load() {
+            Ok(state) => state,
+            Err(e) => {
+                // Usually a write in progress; the next event re-reads it
+                debug!("Failed to read state: {}", e);
+                continue;
+            }
+        };
+        if !is_change(&last, &state) {
+            continue;
+        }
+
+        if !emit(&ChangeEvent::new(Some(&last), &state))? {
+            return Ok(());
+        }
+        last = state;
+    }
+}
+
+/// Load the colors of the state's variant
+fn load_colors(themes: &[ThemeInfo], state: &State) -> Result<BTreeMap<String, Color>> {
+    let theme_info = theme::get_theme(themes, &state.current_theme).ok_or_else(|| {
+        VogixError::InvalidTheme(format!("Theme '{}' not found", state.current_theme))
+    })?;
+    let config = Config::load()?;
+    let colors = theme::load_variant_colors(&config, &theme_info, &state.current_variant)?;
+    Ok(colors.into_iter().collect())
+}
+
+fn is_watched(name: &OsStr) -> bool {
+    WATCHED_FILES.iter().any(|file| name == *file)
+}
+
+/// Print `event` as one line of JSON, returning false once the reader has gone away
+fn emit(event: &ChangeEvent) -> Result<bool> {
+    let line = serde_json::to_string(event).map_err(VogixError::JsonSerialize)?;
+    match writeln!(io::stdout(), "{}", line) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::types::VariantInfo;
+
+    fn state(theme: &str, variant: &str) -> State {
+        State {
+            current_scheme: Scheme::Base16,
+            current_theme: theme.to_string(),
+            current_variant: variant.to_string(),
+            last_applied: None,
+            provenance: None,
+        }
+    }
+
+    fn themes() -> Vec<ThemeInfo> {
+        vec![ThemeInfo {
+            name: "gruvbox".to_string(),
+            scheme: Scheme::Base16,
+            variants: vec![
+                VariantInfo {
+                    name: "light".to_string(),
+                    polarity: "light".to_string(),
+                    order: 0,
+                },
+                VariantInfo {
+                    name: "dark".to_string(),
+                    polarity: "dark".to_string(),
+                    order: 1,
+                },
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_selection_polarity() {
+        let selection = Selection::new(&state("gruvbox", "light"), &themes());
+        assert_eq!(selection.polarity.as_deref(), Some("light"));
+
+        let unknown = Selection::new(&state("nord", "dark"), &themes());
+        assert_eq!(unknown.polarity, None);
+    }
+
+    #[test]
+    fn test_event_json() {
+        let event = ChangeEvent {
+            old: Some(Selection::new(&state("gruvbox", "dark"), &themes())),
+            new: Selection::new(&state("gruvbox", "light"), &themes()),
+            colors: BTreeMap::from([("base00".to_string(), Color::rgb(0xfb, 0xf1, 0xc7))]),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            concat!(
+                r#"{"old":{"scheme":"base16","theme":"gruvbox","variant":"dark","polarity":"dark"},"#,
+                r#""new":{"scheme":"base16","theme":"gruvbox","variant":"light","polarity":"light"},"#,
+                r##""colors":{"base00":"#fbf1c7"}}"##
+            )
+        );
+    }
+
+    #[test]
+    fn test_initial_event_has_no_old_selection() {
+        let event = ChangeEvent {
+            old: None,
+            new: Selection::new(&state("gruvbox", "dark"), &themes()),
+            colors: BTreeMap::new(),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert!(value["old"].is_null());
+    }
+
+    #[test]
+    fn test_is_watched() {
+        assert!(is_watched(OsStr::new("state.toml")));
+        assert!(is_watched(OsStr::new("current-theme")));
+        assert!(!is_watched(OsStr::new("history.toml")));
+        assert!(!is_watched(OsStr::new("state.toml.tmp")));
+    }
+}
@@ -17,12 +17,18 @@ mod types;
 
 use crate::errors::{Result, VogixError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
 
 // Re-export types
-pub use types::{AppMetadata, TemplatesConfig, ThemeSourcesConfig};
+#[cfg(any(feature = "dbus", test))]
+pub use types::DbusArg;
+pub use types::{
+    AppMetadata, AutoConfig, DbusReload, HooksConfig, ManifestIssue, ProcessMatch, ReloadMethod,
+    SignalReload, SwayIpcReload, TemplatesConfig, ThemeSourcesConfig,
+};
 
 /// Main configuration loaded from runtime manifest
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,6 +38,8 @@ pub struct Config {
     pub apps: HashMap<String, AppMetadata>,
     pub templates: Option<TemplatesConfig>,
     pub theme_sources: Option<ThemeSourcesConfig>,
+    pub auto: Option<AutoConfig>,
+    pub hooks: Option<HooksConfig>,
 }
 
 impl Default for Config {
@@ -42,6 +50,8 @@ impl Default for Config {
             apps: HashMap::new(),
             templates: None,
             theme_sources: None,
+            auto: None,
+            hooks: None,
         }
     }
 }
@@ -49,14 +59,17 @@ impl Default for Config {
 impl Config {
     /// Load configuration from the system config (/etc/vogix/config.toml)
     pub fn load() -> Result<Self> {
-        let manifest_path = Self::manifest_path()?;
+        Self::load_from(&Self::manifest_path()?)
+    }
 
+    /// Load configuration from the manifest at `manifest_path`
+    pub fn load_from(manifest_path: &Path) -> Result<Self> {
         if !manifest_path.exists() {
             // Return default config if manifest doesn't exist
             return Ok(Config::default());
         }
 
-        let contents = fs::read_to_string(&manifest_path)?;
+        let contents = fs::read_to_string(manifest_path)?;
         let manifest: toml::Value = toml::from_str(&contents).map_err(VogixError::TomlParse)?;
 
         // Extract config from manifest structure
@@ -75,7 +88,10 @@ impl Config {
             .to_string();
 
         // Parse app metadata from [apps] section
-        let apps = Self::parse_apps(&manifest);
+        let apps = Self::parse_apps(&contents).map_err(|issues| VogixError::InvalidManifest {
+            path: manifest_path.to_path_buf(),
+            issues,
+        })?;
 
         // Parse templates config
         let templates = Self::parse_templates(&manifest);
@@ -83,53 +99,79 @@ impl Config {
         // Parse theme sources config
         let theme_sources = Self::parse_theme_sources(&manifest);
 
+        // Parse automatic polarity switching config
+        let auto = Self::parse_auto(&manifest)?;
+
+        // Parse hook commands run around a switch
+        let hooks = Self::parse_hooks(&manifest)?;
+
         Ok(Config {
             default_theme,
             default_variant,
             apps,
             templates,
             theme_sources,
+            auto,
+            hooks,
         })
     }
 
-    /// Parse the [apps] section from manifest
-    fn parse_apps(manifest: &toml::Value) -> HashMap<String, AppMetadata> {
-        manifest
-            .get("apps")
-            .and_then(|a| a.as_table())
-            .map(|apps_table| {
-                apps_table
-                    .iter()
-                    .filter_map(|(app_name, app_data)| {
-                        let config_path = app_data.get("config_path")?.as_str()?.to_string();
-                        let reload_method = app_data.get("reload_method")?.as_str()?.to_string();
-                        let reload_signal = app_data
-                            .get("reload_signal")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-                        let process_name = app_data
-                            .get("process_name")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-                        let reload_command = app_data
-                            .get("reload_command")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-
-                        Some((
-                            app_name.clone(),
-                            AppMetadata {
-                                config_path,
-                                reload_method,
-                                reload_signal,
-                                process_name,
-                                reload_command,
-                            },
-                        ))
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    /// Parse and validate the [apps] section from the manifest text
+    ///
+    /// Every app is checked, so all misconfigured apps are reported together
+    /// with the position of their table.
+    pub fn parse_apps(
+        contents: &str,
+    ) -> std::result::Result<HashMap<String, AppMetadata>, Vec<ManifestIssue>> {
+        #[derive(Deserialize)]
+        struct AppsSection {
+            #[serde(default)]
+            apps: BTreeMap<String, Spanned<toml::Value>>,
+        }
+
+        let section: AppsSection = toml::from_str(contents).map_err(|e| {
+            let (line, column) = e
+                .span()
+                .map(|span| line_column(contents, span.start))
+                .unwrap_or((1, 1));
+            vec![ManifestIssue {
+                line,
+                column,
+                key: "apps".to_string(),
+                message: e.message().to_string(),
+            }]
+        })?;
+
+        let mut apps = HashMap::new();
+        let mut issues = Vec::new();
+
+        for (app_name, spanned) in section.apps {
+            let (line, column) = line_column(contents, spanned.span().start);
+            let parsed = spanned
+                .into_inner()
+                .try_into::<AppMetadata>()
+                .map_err(|e| e.message().to_string())
+                .and_then(|app| app.reload_method.validate().map(|()| app));
+
+            match parsed {
+                Ok(app) => {
+                    apps.insert(app_name, app);
+                }
+                Err(message) => issues.push(ManifestIssue {
+                    line,
+                    column,
+                    key: format!("apps.{}", app_name),
+                    message,
+                }),
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(apps)
+        } else {
+            issues.sort_by_key(|issue| (issue.line, issue.column));
+            Err(issues)
+        }
     }
 
     /// Parse the [templates] section from manifest
@@ -157,17 +199,52 @@ impl Config {
                 let base16 = t.get("base16")?.as_str()?;
                 let base24 = t.get("base24")?.as_str()?;
                 let ansi16 = t.get("ansi16")?.as_str()?;
+                let fill_missing = t
+                    .get("fill_missing")
+                    .and_then(|f| f.as_bool())
+                    .unwrap_or(false);
                 Some(ThemeSourcesConfig {
                     vogix16: PathBuf::from(vogix16),
                     base16: PathBuf::from(base16),
                     base24: PathBuf::from(base24),
                     ansi16: PathBuf::from(ansi16),
+                    fill_missing,
                 })
             })
     }
 
+    /// Parse the [auto] section from manifest
+    ///
+    /// Unlike the other optional sections, a present but malformed [auto]
+    /// is an error rather than silently disabling the schedule.
+    pub fn parse_auto(manifest: &toml::Value) -> Result<Option<AutoConfig>> {
+        manifest
+            .get("auto")
+            .map(|auto| {
+                auto.clone()
+                    .try_into::<AutoConfig>()
+                    .map_err(|e| VogixError::Config(format!("[auto] {}", e.message())))
+            })
+            .transpose()
+    }
+
+    /// Parse the [hooks] section from manifest
+    ///
+    /// Malformed hooks are an error, so a pre-apply check is never skipped silently.
+    pub fn parse_hooks(manifest: &toml::Value) -> Result<Option<HooksConfig>> {
+        manifest
+            .get("hooks")
+            .map(|hooks| {
+                hooks
+                    .clone()
+                    .try_into::<HooksConfig>()
+                    .map_err(|e| VogixError::Config(format!("[hooks] {}", e.message())))
+            })
+            .transpose()
+    }
+
     /// Get the config path (~/.local/state/vogix/config.toml)
-    fn manifest_path() -> Result<PathBuf> {
+    pub fn manifest_path() -> Result<PathBuf> {
         Ok(Self::state_dir().join("config.toml"))
     }
 
@@ -207,3 +284,16 @@ impl Config {
         Self::data_dir().join("themes")
     }
 }
+
+/// 1-based line and column of a byte offset in `text`
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
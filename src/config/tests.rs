@@ -194,24 +194,284 @@ reload_method = "command"
 reload_command = "polybar-msg cmd restart"
 "##;
 
-    let manifest_value: toml::Value = toml::from_str(manifest).unwrap();
-    let apps = Config::parse_apps(&manifest_value);
+    let apps = Config::parse_apps(manifest).unwrap();
 
     assert_eq!(apps.len(), 3);
 
     let alacritty = apps.get("alacritty").unwrap();
-    assert_eq!(alacritty.reload_method, "touch");
-    assert!(alacritty.reload_signal.is_none());
+    assert_eq!(alacritty.reload_method, ReloadMethod::Touch);
 
     let btop = apps.get("btop").unwrap();
-    assert_eq!(btop.reload_method, "signal");
-    assert_eq!(btop.reload_signal.as_deref(), Some("USR1"));
-    assert_eq!(btop.process_name.as_deref(), Some("btop"));
+    assert_eq!(
+        btop.reload_method,
+        ReloadMethod::Signal(SignalReload {
+            reload_signal: "USR1".to_string(),
+            process_name: Some("btop".to_string()),
+            ..Default::default()
+        })
+    );
 
     let polybar = apps.get("polybar").unwrap();
-    assert_eq!(polybar.reload_method, "command");
     assert_eq!(
-        polybar.reload_command.as_deref(),
-        Some("polybar-msg cmd restart")
+        polybar.reload_method,
+        ReloadMethod::Command {
+            reload_command: "polybar-msg cmd restart".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_parse_apps_sway_ipc() {
+    let manifest = r##"
+[apps.sway]
+config_path = "/home/user/.config/sway/config"
+reload_method = "sway-ipc"
+ipc_commands = ["reload", "bar mode dock"]
+ipc_socket = "/run/user/1000/sway-ipc.sock"
+"##;
+
+    let apps = Config::parse_apps(manifest).unwrap();
+
+    let sway = apps.get("sway").unwrap();
+    assert_eq!(
+        sway.reload_method,
+        ReloadMethod::SwayIpc(SwayIpcReload {
+            ipc_commands: Some(vec!["reload".to_string(), "bar mode dock".to_string()]),
+            ipc_socket: Some("/run/user/1000/sway-ipc.sock".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_parse_apps_dbus() {
+    let manifest = r##"
+[apps.dunst]
+config_path = "/home/user/.config/dunst/dunstrc"
+reload_method = "dbus"
+dbus_destination = "org.freedesktop.Notifications"
+dbus_path = "/org/freedesktop/Notifications"
+dbus_interface = "org.dunstproject.cmd0"
+dbus_method = "ConfigReload"
+dbus_args = [["/home/user/.config/dunst/dunstrc"]]
+"##;
+
+    let apps = Config::parse_apps(manifest).unwrap();
+
+    let dunst = apps.get("dunst").unwrap();
+    let ReloadMethod::Dbus(dbus) = &dunst.reload_method else {
+        panic!("expected dbus reload method, got {:?}", dunst.reload_method);
+    };
+    assert!(dbus.dbus_bus.is_none());
+    assert_eq!(dbus.dbus_destination, "org.freedesktop.Notifications");
+    assert_eq!(
+        dbus.dbus_interface.as_deref(),
+        Some("org.dunstproject.cmd0")
+    );
+    assert_eq!(dbus.dbus_method, "ConfigReload");
+    assert_eq!(
+        dbus.args().unwrap(),
+        vec![DbusArg::StringArray(vec![
+            "/home/user/.config/dunst/dunstrc".to_string()
+        ])]
     );
 }
+
+#[test]
+fn test_dbus_arg_types() {
+    let parse = |value: &str| DbusArg::parse(&toml::from_str::<toml::Table>(value).unwrap()["v"]);
+    assert_eq!(parse("v = \"x\""), Ok(DbusArg::String("x".to_string())));
+    assert_eq!(parse("v = 5"), Ok(DbusArg::Int32(5)));
+    assert_eq!(parse("v = 5000000000"), Ok(DbusArg::Int64(5_000_000_000)));
+    assert_eq!(parse("v = true"), Ok(DbusArg::Bool(true)));
+    assert_eq!(parse("v = 0.5"), Ok(DbusArg::Double(0.5)));
+    assert!(
+        parse("v = [1]")
+            .unwrap_err()
+            .contains("only contain strings")
+    );
+    assert!(parse("v = { a = 1 }").unwrap_err().contains("unsupported"));
+}
+
+#[test]
+fn test_parse_apps_reload_timeout_and_process_match() {
+    let manifest = r##"
+[apps.waybar]
+config_path = "/home/user/.config/waybar/style.css"
+reload_method = "signal"
+reload_signal = "SIGUSR2"
+process_name = "^waybar( |$)"
+process_match = "cmdline"
+process_same_session = true
+reload_timeout = 3
+critical = true
+"##;
+
+    let apps = Config::parse_apps(manifest).unwrap();
+
+    let waybar = apps.get("waybar").unwrap();
+    assert_eq!(waybar.reload_timeout, Some(3));
+    assert!(waybar.critical);
+    let ReloadMethod::Signal(signal) = &waybar.reload_method else {
+        panic!(
+            "expected signal reload method, got {:?}",
+            waybar.reload_method
+        );
+    };
+    assert_eq!(signal.process_match, ProcessMatch::Cmdline);
+    assert!(signal.process_same_session);
+    assert!(signal.process_cgroup.is_none());
+}
+
+#[test]
+fn test_parse_apps_reports_every_misconfigured_app() {
+    let manifest = r##"[default]
+theme = "aikido"
+
+[apps.alacritty]
+config_path = "/home/user/.config/alacritty/alacritty.toml"
+reload_method = "touch"
+
+[apps.btop]
+config_path = "/home/user/.config/btop/btop.conf"
+reload_method = "signal"
+
+[apps.kitty]
+reload_method = "touch"
+
+[apps.mako]
+config_path = "/home/user/.config/mako/config"
+reload_method = "restart"
+
+[apps.waybar]
+config_path = "/home/user/.config/waybar/style.css"
+reload_method = "signal"
+reload_signal = "USR2"
+process_name = "waybar("
+process_match = "cmdline"
+"##;
+
+    let issues = Config::parse_apps(manifest).unwrap_err();
+
+    let located: Vec<(usize, usize, &str)> = issues
+        .iter()
+        .map(|issue| (issue.line, issue.column, issue.key.as_str()))
+        .collect();
+    assert_eq!(
+        located,
+        vec![
+            (8, 1, "apps.btop"),
+            (12, 1, "apps.kitty"),
+            (15, 1, "apps.mako"),
+            (19, 1, "apps.waybar"),
+        ]
+    );
+    assert!(issues[0].message.contains("reload_signal"));
+    assert!(issues[1].message.contains("config_path"));
+    assert!(issues[2].message.contains("restart"));
+    assert!(issues[3].message.contains("regex"));
+    assert!(
+        issues[0]
+            .to_string()
+            .starts_with("line 8, column 1: [apps.btop]")
+    );
+}
+
+#[test]
+fn test_parse_apps_missing_section() {
+    let apps = Config::parse_apps("[default]\ntheme = \"aikido\"\n").unwrap();
+    assert!(apps.is_empty());
+}
+
+#[test]
+fn test_parse_apps_rejects_unknown_process_match() {
+    let manifest = r##"
+[apps.btop]
+config_path = "/home/user/.config/btop/btop.conf"
+reload_method = "signal"
+reload_signal = "USR1"
+process_match = "pid"
+"##;
+
+    let issues = Config::parse_apps(manifest).unwrap_err();
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message.contains("pid"));
+}
+
+#[test]
+fn test_line_column() {
+    let text = "ab\ncdé\nf";
+    assert_eq!(line_column(text, 0), (1, 1));
+    assert_eq!(line_column(text, 4), (2, 2));
+    assert_eq!(line_column(text, 8), (3, 1));
+    assert_eq!(line_column(text, 7), (2, 4));
+}
+
+#[test]
+fn test_parse_auto_times() {
+    let manifest: toml::Value = toml::from_str(
+        r#"
+[auto]
+light = "07:00"
+dark = "19:30"
+interval = 120
+"#,
+    )
+    .unwrap();
+
+    let auto = Config::parse_auto(&manifest).unwrap().unwrap();
+    assert_eq!(auto.light.as_deref(), Some("07:00"));
+    assert_eq!(auto.dark.as_deref(), Some("19:30"));
+    assert_eq!(auto.interval, Some(120));
+    assert!(auto.latitude.is_none());
+}
+
+#[test]
+fn test_parse_auto_missing_section() {
+    let manifest: toml::Value = toml::from_str("[default]\ntheme = \"aikido\"\n").unwrap();
+    assert!(Config::parse_auto(&manifest).unwrap().is_none());
+}
+
+#[test]
+fn test_parse_auto_rejects_unknown_key() {
+    let manifest: toml::Value = toml::from_str("[auto]\nsunrise = \"07:00\"\n").unwrap();
+    let err = Config::parse_auto(&manifest).unwrap_err();
+    assert!(err.to_string().contains("sunrise"));
+}
+
+#[test]
+fn test_parse_hooks() {
+    let manifest: toml::Value = toml::from_str(
+        r#"
+[hooks]
+pre_apply = ["test -n \"$WAYLAND_DISPLAY\""]
+post_apply = ["swww img ~/walls/$VOGIX_POLARITY.png", "notify-send vogix"]
+timeout = 5
+"#,
+    )
+    .unwrap();
+
+    let hooks = Config::parse_hooks(&manifest).unwrap().unwrap();
+    assert_eq!(hooks.pre_apply.len(), 1);
+    assert_eq!(hooks.post_apply[1], "notify-send vogix");
+    assert_eq!(hooks.timeout, Some(5));
+}
+
+#[test]
+fn test_parse_hooks_defaults_to_empty_lists() {
+    let manifest: toml::Value =
+        toml::from_str("[hooks]\npost_apply = [\"notify-send vogix\"]\n").unwrap();
+    let hooks = Config::parse_hooks(&manifest).unwrap().unwrap();
+    assert!(hooks.pre_apply.is_empty());
+    assert!(hooks.timeout.is_none());
+}
+
+#[test]
+fn test_parse_hooks_rejects_malformed_section() {
+    let manifest: toml::Value = toml::from_str("[hooks]\npost_apply = \"notify-send\"\n").unwrap();
+    let err = Config::parse_hooks(&manifest).unwrap_err();
+    assert!(err.to_string().starts_with("config error: [hooks]"));
+
+    let manifest: toml::Value = toml::from_str("[hooks]\nafter_apply = []\n").unwrap();
+    let err = Config::parse_hooks(&manifest).unwrap_err();
+    assert!(err.to_string().contains("after_apply"));
+}
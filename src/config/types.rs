@@ -19,17 +19,227 @@ pub struct ThemeSourcesConfig {
     pub base16: PathBuf,
     pub base24: PathBuf,
     pub ansi16: PathBuf,
+    /// Fill colors missing from theme files with their scheme's fallbacks
+    #[serde(default)]
+    pub fill_missing: bool,
+}
+
+/// Automatic polarity switching from the [auto] section
+///
+/// Either `light`/`dark` local times ("HH:MM") or a `latitude`/`longitude`
+/// pair for sunrise/sunset. Coordinates take precedence when both are set.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AutoConfig {
+    /// Local time light variants start
+    pub light: Option<String>,
+    /// Local time dark variants start
+    pub dark: Option<String>,
+    /// Latitude in degrees, north positive
+    pub latitude: Option<f64>,
+    /// Longitude in degrees, east positive
+    pub longitude: Option<f64>,
+    /// Seconds between checks in `vogix auto` daemon mode
+    pub interval: Option<u64>,
+}
+
+/// Commands run around a theme switch, from the [hooks] section
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Run before anything is switched; a failure aborts the switch
+    #[serde(default)]
+    pub pre_apply: Vec<String>,
+    /// Run after apps are reloaded; failures are only reported
+    #[serde(default)]
+    pub post_apply: Vec<String>,
+    /// Seconds each hook may run before it is killed
+    pub timeout: Option<u64>,
 }
 
 /// Metadata for an application that can be themed
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AppMetadata {
     pub config_path: String,
-    pub reload_method: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reload_signal: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Reload method and its settings, tagged by the `reload_method` key
+    #[serde(flatten)]
+    pub reload_method: ReloadMethod,
+    /// Seconds the reload may take before it is abandoned (a `reload_command` is killed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload_timeout: Option<u64>,
+    /// Roll the theme switch back if this app fails to reload
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub critical: bool,
+}
+
+/// How an application is told to pick up a new theme
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "reload_method", rename_all = "kebab-case")]
+pub enum ReloadMethod {
+    /// Send a Unix signal to the app's processes
+    Signal(SignalReload),
+    /// Run a shell command
+    Command { reload_command: String },
+    /// Touch the config symlink so file watchers notice the change
+    Touch,
+    /// Call a D-Bus method
+    Dbus(DbusReload),
+    /// Run Sway/i3 IPC commands
+    SwayIpc(SwayIpcReload),
+    /// Changes take effect on next launch
+    #[default]
+    None,
+}
+
+/// Settings for the `signal` reload method
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SignalReload {
+    pub reload_signal: String,
+    /// Process to signal (defaults to the app name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub process_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reload_command: Option<String>,
+    /// How `process_name` is matched
+    #[serde(default)]
+    pub process_match: ProcessMatch,
+    /// Only signal processes whose cgroup path contains this string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_cgroup: Option<String>,
+    /// Only signal processes in the same login session as vogix
+    #[serde(default)]
+    pub process_same_session: bool,
+}
+
+/// How a signal target's `process_name` is compared against running processes
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessMatch {
+    /// Kernel process name (`/proc/PID/comm`, truncated to 15 bytes)
+    #[default]
+    Comm,
+    /// Basename of the executable
+    Exe,
+    /// Regex over the full command line
+    Cmdline,
+}
+
+/// Settings for the `dbus` reload method
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DbusReload {
+    /// Bus for the call: "session" (default), "system" or a bus address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dbus_bus: Option<String>,
+    /// Well-known bus name to call (e.g. "org.freedesktop.Notifications")
+    pub dbus_destination: String,
+    /// Object path of the reload method (e.g. "/org/freedesktop/Notifications")
+    pub dbus_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dbus_interface: Option<String>,
+    pub dbus_method: String,
+    /// Method arguments (strings, integers, booleans, floats or string arrays)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dbus_args: Vec<toml::Value>,
+}
+
+impl DbusReload {
+    /// The method arguments, typed as they are sent
+    pub fn args(&self) -> std::result::Result<Vec<DbusArg>, String> {
+        self.dbus_args.iter().map(DbusArg::parse).collect()
+    }
+}
+
+/// A `dbus_args` entry, by the D-Bus type it is sent as
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbusArg {
+    /// `s`
+    String(String),
+    /// `i`
+    Int32(i32),
+    /// `x`, for integers out of `i32` range
+    Int64(i64),
+    /// `b`
+    Bool(bool),
+    /// `d`
+    Double(f64),
+    /// `as`
+    StringArray(Vec<String>),
+}
+
+impl DbusArg {
+    /// Type a manifest argument, failing for values D-Bus calls can't take
+    pub fn parse(arg: &toml::Value) -> std::result::Result<Self, String> {
+        match arg {
+            toml::Value::String(s) => Ok(Self::String(s.clone())),
+            toml::Value::Integer(i) => Ok(match i32::try_from(*i) {
+                Ok(small) => Self::Int32(small),
+                Err(_) => Self::Int64(*i),
+            }),
+            toml::Value::Boolean(b) => Ok(Self::Bool(*b)),
+            toml::Value::Float(f) => Ok(Self::Double(*f)),
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().map(String::from))
+                .collect::<Option<Vec<String>>>()
+                .map(Self::StringArray)
+                .ok_or_else(|| "dbus_args arrays may only contain strings".to_string()),
+            other => Err(format!("unsupported dbus_args value: {}", other)),
+        }
+    }
+}
+
+/// Settings for the `sway-ipc` reload method
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SwayIpcReload {
+    /// Commands to run (defaults to `reload`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipc_commands: Option<Vec<String>>,
+    /// Compositor IPC socket path (defaults to `$I3SOCK` / `$SWAYSOCK`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipc_socket: Option<String>,
+}
+
+impl ReloadMethod {
+    /// Check settings that deserialization alone can't
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self {
+            Self::Signal(signal) => {
+                if signal.reload_signal.trim().is_empty() {
+                    return Err("reload_signal must not be empty".to_string());
+                }
+                if signal.process_match == ProcessMatch::Cmdline
+                    && let Some(pattern) = &signal.process_name
+                {
+                    regex::Regex::new(pattern)
+                        .map_err(|e| format!("invalid process_name regex: {}", e))?;
+                }
+                Ok(())
+            }
+            Self::Command { reload_command } if reload_command.trim().is_empty() => {
+                Err("reload_command must not be empty".to_string())
+            }
+            Self::Dbus(dbus) => dbus.args().map(drop),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A misconfigured entry in the manifest, with its position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestIssue {
+    /// 1-based line of the offending table
+    pub line: usize,
+    /// 1-based column of the offending table
+    pub column: usize,
+    /// Dotted key of the table (e.g. "apps.btop")
+    pub key: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: [{}] {}",
+            self.line, self.column, self.key, self.message
+        )
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Uses thiserror for clean, idiomatic error handling with proper error chaining.
 
+use crate::config::ManifestIssue;
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -21,6 +22,17 @@ pub enum VogixError {
     #[error("config error: {0}")]
     Config(String),
 
+    /// Manifest has misconfigured entries, all reported at once
+    #[error(
+        "invalid manifest {}:\n  {}",
+        .path.display(),
+        .issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  ")
+    )]
+    InvalidManifest {
+        path: PathBuf,
+        issues: Vec<ManifestIssue>,
+    },
+
     /// Failed to parse TOML file
     #[error("failed to parse TOML")]
     TomlParse(#[source] toml::de::Error),
@@ -29,10 +41,43 @@ pub enum VogixError {
     #[error("failed to serialize TOML")]
     TomlSerialize(#[source] toml::ser::Error),
 
+    /// Failed to serialize JSON
+    #[error("failed to serialize JSON")]
+    JsonSerialize(#[source] serde_json::Error),
+
+    /// Failed to parse JSON
+    #[error("failed to parse JSON")]
+    JsonParse(#[source] serde_json::Error),
+
     /// Failed to parse YAML file
     #[error("failed to parse YAML")]
     YamlParse(#[source] serde_yaml::Error),
 
+    /// A theme file has a color value that can't be parsed
+    #[error("invalid color {value:?} for {key} in {}", .path.display())]
+    InvalidColor {
+        path: PathBuf,
+        key: String,
+        value: String,
+    },
+
+    /// A theme file lacks colors its scheme requires, or has unknown ones
+    #[error(
+        "incomplete theme {}: {}",
+        .path.display(),
+        [("missing", .missing), ("unknown", .unknown)]
+            .iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(kind, keys)| format!("{} {}", kind, keys.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    )]
+    IncompleteTheme {
+        path: PathBuf,
+        missing: Vec<String>,
+        unknown: Vec<String>,
+    },
+
     /// Theme specification is invalid
     #[error("invalid theme: {0}")]
     InvalidTheme(String),
@@ -57,6 +102,18 @@ pub enum VogixError {
         source: Option<io::Error>,
     },
 
+    /// Daemon could not be started or reported a failure
+    #[error("daemon error: {0}")]
+    Daemon(String),
+
+    /// Rendered variants don't match their sources
+    #[error("cache error: {0}")]
+    Cache(String),
+
+    /// A hook command failed or could not be run
+    #[error("hook failed: {0}")]
+    Hook(String),
+
     /// Template rendering failed
     #[error("template error: {0}")]
     Template(#[source] tera::Error),
@@ -0,0 +1,312 @@
+use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
+use crate::state::{State, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of entries kept; the oldest are dropped first
+pub const MAX_ENTRIES: usize = 50;
+
+/// One applied scheme/theme/variant selection
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub scheme: Scheme,
+    pub theme: String,
+    pub variant: String,
+    /// When this entry was recorded (RFC 3339)
+    pub applied_at: String,
+}
+
+impl HistoryEntry {
+    /// Create an entry for the selection in `state`, timestamped now
+    pub fn from_state(state: &State) -> Self {
+        HistoryEntry {
+            scheme: state.current_scheme,
+            theme: state.current_theme.clone(),
+            variant: state.current_variant.clone(),
+            applied_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether `state` has the same selection as this entry
+    pub fn matches(&self, state: &State) -> bool {
+        self.scheme == state.current_scheme
+            && self.theme == state.current_theme
+            && self.variant == state.current_variant
+    }
+
+    /// Copy this entry's selection into `state`
+    pub fn apply_to(&self, state: &mut State) {
+        state.current_scheme = self.scheme;
+        state.current_theme = self.theme.clone();
+        state.current_variant = self.variant.clone();
+    }
+}
+
+/// Bounded log of theme changes with an undo/redo cursor
+///
+/// `position` indexes the entry that is currently applied. Undo moves it back,
+/// redo moves it forward, and recording a new change drops everything after it.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct History {
+    #[serde(default)]
+    pub position: usize,
+    #[serde(default)]
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Load history from the default location next to state.toml
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::default_history_path()?)
+    }
+
+    /// Load history from a specific path
+    pub fn load_from(history_path: &Path) -> Result<Self> {
+        if !history_path.exists() {
+            return Ok(History::default());
+        }
+
+        let contents = fs::read_to_string(history_path)?;
+        let mut history: History = toml::from_str(&contents).map_err(VogixError::TomlParse)?;
+        history.position = history
+            .position
+            .min(history.entries.len().saturating_sub(1));
+
+        Ok(history)
+    }
+
+    /// Save history to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::default_history_path()?)
+    }
+
+    /// Save history to a specific path, replacing it atomically like the state
+    pub fn save_to(&self, history_path: &Path) -> Result<()> {
+        if let Some(parent) = history_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents = toml::to_string_pretty(self).map_err(VogixError::TomlSerialize)?;
+        write_atomic(history_path, contents.as_bytes())
+    }
+
+    /// Get the default history file path (~/.local/state/vogix/history.toml)
+    fn default_history_path() -> Result<PathBuf> {
+        Ok(State::state_dir()?.join("history.toml"))
+    }
+
+    /// The currently applied entry
+    pub fn current(&self) -> Option<&HistoryEntry> {
+        self.entries.get(self.position)
+    }
+
+    /// Record a change from `previous` to `applied`
+    ///
+    /// `previous` is recorded first if the log doesn't already end on it, so
+    /// the first change (or a change made while history was out of date) can
+    /// still be undone.
+    pub fn record(&mut self, previous: &State, applied: &State) {
+        self.entries.truncate(self.position + 1);
+
+        if !self.current().is_some_and(|entry| entry.matches(previous)) {
+            self.entries.push(HistoryEntry::from_state(previous));
+        }
+        self.entries.push(HistoryEntry::from_state(applied));
+
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    /// Step back one entry, returning the entry to apply
+    pub fn undo(&mut self) -> Option<&HistoryEntry> {
+        if self.position == 0 || self.entries.is_empty() {
+            return None;
+        }
+        self.position -= 1;
+        self.current()
+    }
+
+    /// Step forward one entry, returning the entry to apply
+    pub fn redo(&mut self) -> Option<&HistoryEntry> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.position += 1;
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn state(theme: &str, variant: &str) -> State {
+        State {
+            current_scheme: Scheme::Base16,
+            current_theme: theme.to_string(),
+            current_variant: variant.to_string(),
+            last_applied: None,
+            provenance: None,
+        }
+    }
+
+    fn selections(history: &History) -> Vec<String> {
+        history
+            .entries
+            .iter()
+            .map(|e| format!("{}-{}", e.theme, e.variant))
+            .collect()
+    }
+
+    #[test]
+    fn test_record_first_change_keeps_previous() {
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+
+        assert_eq!(selections(&history), vec!["gruvbox-dark", "nord-dark"]);
+        assert_eq!(history.position, 1);
+    }
+
+    #[test]
+    fn test_record_consecutive_changes() {
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+        history.record(&state("nord", "dark"), &state("nord", "light"));
+
+        assert_eq!(
+            selections(&history),
+            vec!["gruvbox-dark", "nord-dark", "nord-light"]
+        );
+    }
+
+    #[test]
+    fn test_record_after_external_change() {
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+        // state.toml was changed without going through vogix
+        history.record(&state("dracula", "dark"), &state("nord", "light"));
+
+        assert_eq!(
+            selections(&history),
+            vec!["gruvbox-dark", "nord-dark", "dracula-dark", "nord-light"]
+        );
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+        history.record(&state("nord", "dark"), &state("dracula", "dark"));
+
+        assert_eq!(history.undo().unwrap().theme, "nord");
+        assert_eq!(history.undo().unwrap().theme, "gruvbox");
+        assert!(history.undo().is_none());
+        assert_eq!(history.position, 0);
+
+        assert_eq!(history.redo().unwrap().theme, "nord");
+        assert_eq!(history.redo().unwrap().theme, "dracula");
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn test_record_after_undo_drops_redo_entries() {
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+        history.record(&state("nord", "dark"), &state("dracula", "dark"));
+        history.undo();
+
+        history.record(&state("nord", "dark"), &state("rose-pine", "moon"));
+
+        assert_eq!(
+            selections(&history),
+            vec!["gruvbox-dark", "nord-dark", "rose-pine-moon"]
+        );
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn test_empty_history_undo_redo() {
+        let mut history = History::default();
+        assert!(history.undo().is_none());
+        assert!(history.redo().is_none());
+        assert!(history.current().is_none());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = History::default();
+        for i in 0..MAX_ENTRIES + 10 {
+            let previous = state("theme", &i.to_string());
+            let applied = state("theme", &(i + 1).to_string());
+            history.record(&previous, &applied);
+        }
+
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(history.position, MAX_ENTRIES - 1);
+        assert_eq!(history.entries[0].variant, "11");
+        assert_eq!(history.current().unwrap().variant, "60");
+    }
+
+    #[test]
+    fn test_history_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = temp_dir.path().join("history.toml");
+
+        let mut history = History::default();
+        history.record(&state("gruvbox", "dark"), &state("nord", "dark"));
+        history.undo();
+        history.save_to(&history_path).unwrap();
+
+        let loaded = History::load_from(&history_path).unwrap();
+        assert_eq!(loaded.position, 0);
+        assert_eq!(loaded.entries, history.entries);
+    }
+
+    #[test]
+    fn test_history_save_replaces_atomically() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = temp_dir.path().join("history.toml");
+        History::default().save_to(&history_path).unwrap();
+        History::default().save_to(&history_path).unwrap();
+
+        // No temporary files are left behind
+        let entries: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec!["history.toml"]);
+    }
+
+    #[test]
+    fn test_history_load_missing_returns_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let loaded = History::load_from(&temp_dir.path().join("history.toml")).unwrap();
+        assert!(loaded.entries.is_empty());
+    }
+
+    #[test]
+    fn test_history_load_clamps_position() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = temp_dir.path().join("history.toml");
+        fs::write(
+            &history_path,
+            r#"position = 7
+
+[[entries]]
+scheme = "base16"
+theme = "nord"
+variant = "dark"
+applied_at = "2026-01-01T00:00:00+00:00"
+"#,
+        )
+        .unwrap();
+
+        let loaded = History::load_from(&history_path).unwrap();
+        assert_eq!(loaded.position, 0);
+    }
+}
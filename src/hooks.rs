@@ -0,0 +1,293 @@
+//! Hook commands run before and after a theme switch.
+//!
+//! Each hook runs through `sh -c` with the new selection in its environment:
+//! `VOGIX_SCHEME`, `VOGIX_THEME`, `VOGIX_VARIANT`, `VOGIX_POLARITY`,
+//! `VOGIX_CACHE_DIR` and one `VOGIX_COLOR_<NAME>` per color.
+
+use crate::color::Color;
+use crate::config::{Config, HooksConfig};
+use crate::errors::{Result, VogixError};
+use crate::reload::run_shell;
+use crate::state::State;
+use crate::theme::{self, types::ThemeInfo};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Seconds a hook may run when `[hooks] timeout` is not set
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// The configured hooks of one switch, with their environment
+pub struct Hooks<'a> {
+    hooks: &'a HooksConfig,
+    env: Vec<(String, String)>,
+}
+
+impl<'a> Hooks<'a> {
+    /// Prepare the hooks for switching to `state`, or None if no hooks are configured
+    ///
+    /// `themes` are the caller's discovered themes, and `config_dir` the
+    /// directory the variant's app configs are read from.
+    pub fn for_switch(
+        config: &'a Config,
+        state: &State,
+        themes: &[ThemeInfo],
+        config_dir: &Path,
+    ) -> Option<Self> {
+        let hooks = config
+            .hooks
+            .as_ref()
+            .filter(|hooks| !hooks.pre_apply.is_empty() || !hooks.post_apply.is_empty())?;
+
+        let theme_info = theme::get_theme(themes, &state.current_theme);
+        if theme_info.is_none() {
+            warn!(
+                "Hooks run without VOGIX_POLARITY and VOGIX_COLOR_* variables: theme '{}' not found",
+                state.current_theme
+            );
+        }
+        let polarity = theme_info.as_ref().and_then(|t| {
+            t.variants
+                .iter()
+                .find(|v| v.name == state.current_variant)
+                .map(|v| v.polarity.clone())
+        });
+        let colors = theme_info
+            .map(|t| theme::load_variant_colors(config, &t, &state.current_variant))
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Hooks run without VOGIX_COLOR_* variables: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        Some(Hooks {
+            hooks,
+            env: hook_env(state, polarity.as_deref(), config_dir, &colors),
+        })
+    }
+
+    /// Run the pre-apply hooks in order, stopping at the first that fails
+    pub fn run_pre_apply(&self) -> Result<()> {
+        for command in &self.hooks.pre_apply {
+            self.run(command).map_err(|message| {
+                VogixError::Hook(format!("pre_apply `{}` {}", command, message))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Run every post-apply hook, warning about those that fail
+    pub fn run_post_apply(&self) {
+        for command in &self.hooks.post_apply {
+            if let Err(message) = self.run(command) {
+                warn!("post_apply hook `{}` {}", command, message);
+            }
+        }
+    }
+
+    fn run(&self, command: &str) -> std::result::Result<(), String> {
+        let timeout = Duration::from_secs(self.hooks.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        debug!("Running hook: {}", command);
+        let output = run_shell(command, &self.env, timeout).map_err(|e| e.to_string())?;
+
+        for line in output.stdout.lines() {
+            debug!("  stdout: {}", line);
+        }
+        for line in output.stderr.lines() {
+            debug!("  stderr: {}", line);
+        }
+
+        match output.status {
+            Some(status) if status.success() => Ok(()),
+            Some(status) => match output.stderr.trim() {
+                "" => Err(format!("failed ({})", status)),
+                stderr => Err(format!("failed ({}): {}", status, stderr)),
+            },
+            None => Err(format!("killed after {}s", timeout.as_secs())),
+        }
+    }
+}
+
+/// Environment variables describing the selection in `state`
+fn hook_env(
+    state: &State,
+    polarity: Option<&str>,
+    config_dir: &Path,
+    colors: &HashMap<String, Color>,
+) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("VOGIX_SCHEME".to_string(), state.current_scheme.to_string()),
+        ("VOGIX_THEME".to_string(), state.current_theme.clone()),
+        ("VOGIX_VARIANT".to_string(), state.current_variant.clone()),
+        (
+            "VOGIX_POLARITY".to_string(),
+            polarity.unwrap_or_default().to_string(),
+        ),
+        (
+            "VOGIX_CACHE_DIR".to_string(),
+            config_dir.display().to_string(),
+        ),
+    ];
+
+    let mut colors: Vec<_> = colors.iter().collect();
+    colors.sort_by_key(|(key, _)| *key);
+    env.extend(
+        colors
+            .into_iter()
+            .map(|(key, color)| (color_var(key), color.to_string())),
+    );
+    env
+}
+
+/// Variable name for a color key, e.g. `base0A` -> `VOGIX_COLOR_BASE0A`
+fn color_var(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("VOGIX_COLOR_{}", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::Scheme;
+
+    fn state() -> State {
+        State {
+            current_scheme: Scheme::Base16,
+            current_theme: "gruvbox".to_string(),
+            current_variant: "dark".to_string(),
+            last_applied: None,
+            provenance: None,
+        }
+    }
+
+    fn hooks(config: &HooksConfig) -> Hooks<'_> {
+        let colors = HashMap::from([("base00".to_string(), Color::rgb(0x28, 0x28, 0x28))]);
+        Hooks {
+            hooks: config,
+            env: hook_env(
+                &state(),
+                Some("dark"),
+                Path::new("/cache/gruvbox/dark"),
+                &colors,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_hook_env() {
+        let colors = HashMap::from([
+            ("base0A".to_string(), Color::rgb(0xfa, 0xbd, 0x2f)),
+            (
+                "foreground-bright".to_string(),
+                Color::rgb(0xeb, 0xdb, 0xb2),
+            ),
+        ]);
+        let env = hook_env(&state(), None, Path::new("/cache/dir"), &colors);
+        let env: HashMap<_, _> = env.into_iter().collect();
+
+        assert_eq!(env["VOGIX_SCHEME"], "base16");
+        assert_eq!(env["VOGIX_THEME"], "gruvbox");
+        assert_eq!(env["VOGIX_VARIANT"], "dark");
+        assert_eq!(env["VOGIX_POLARITY"], "");
+        assert_eq!(env["VOGIX_CACHE_DIR"], "/cache/dir");
+        assert_eq!(env["VOGIX_COLOR_BASE0A"], "#fabd2f");
+        assert_eq!(env["VOGIX_COLOR_FOREGROUND_BRIGHT"], "#ebdbb2");
+    }
+
+    #[test]
+    fn test_hooks_see_environment() {
+        let config = HooksConfig {
+            pre_apply: vec![
+                r#"test "$VOGIX_THEME-$VOGIX_POLARITY" = gruvbox-dark"#.to_string(),
+                r##"test "$VOGIX_COLOR_BASE00" = "#282828""##.to_string(),
+            ],
+            ..HooksConfig::default()
+        };
+        hooks(&config).run_pre_apply().unwrap();
+    }
+
+    #[test]
+    fn test_failing_pre_apply_stops_at_first_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let marker = temp_dir.path().join("ran");
+        let config = HooksConfig {
+            pre_apply: vec![
+                "echo wallpaper missing >&2; exit 3".to_string(),
+                format!("touch {}", marker.display()),
+            ],
+            ..HooksConfig::default()
+        };
+
+        let err = hooks(&config).run_pre_apply().unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with("hook failed: pre_apply `echo wallpaper missing"));
+        assert!(message.contains("exit status: 3"));
+        assert!(message.ends_with("wallpaper missing"));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_failing_post_apply_runs_the_rest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let marker = temp_dir.path().join("ran");
+        let config = HooksConfig {
+            post_apply: vec!["false".to_string(), format!("touch {}", marker.display())],
+            ..HooksConfig::default()
+        };
+
+        hooks(&config).run_post_apply();
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn test_for_switch_without_hooks() {
+        let config = Config::default();
+        assert!(Hooks::for_switch(&config, &state(), &[], Path::new("/cache")).is_none());
+
+        let config = Config {
+            hooks: Some(HooksConfig::default()),
+            ..Config::default()
+        };
+        assert!(Hooks::for_switch(&config, &state(), &[], Path::new("/cache")).is_none());
+    }
+
+    #[test]
+    fn test_for_switch_uses_given_themes() {
+        let config = Config {
+            hooks: Some(HooksConfig {
+                post_apply: vec!["true".to_string()],
+                ..HooksConfig::default()
+            }),
+            ..Config::default()
+        };
+        let themes = [ThemeInfo {
+            name: "gruvbox".to_string(),
+            scheme: Scheme::Base16,
+            variants: vec![crate::theme::types::VariantInfo {
+                name: "dark".to_string(),
+                polarity: "dark".to_string(),
+                order: 0,
+            }],
+        }];
+
+        let hooks = Hooks::for_switch(&config, &state(), &themes, Path::new("/cache")).unwrap();
+        let env: HashMap<_, _> = hooks.env.into_iter().collect();
+        assert_eq!(env["VOGIX_POLARITY"], "dark");
+
+        // Unknown to the caller's themes, so no polarity
+        let hooks = Hooks::for_switch(&config, &state(), &[], Path::new("/cache")).unwrap();
+        let env: HashMap<_, _> = hooks.env.into_iter().collect();
+        assert_eq!(env["VOGIX_POLARITY"], "");
+    }
+}
@@ -1,9 +1,13 @@
 mod cache;
 mod cli;
+mod color;
 mod commands;
 mod config;
 mod errors;
+mod history;
+mod hooks;
 mod reload;
+mod schedule;
 mod scheme;
 mod state;
 mod symlink;
@@ -11,10 +15,11 @@ mod template;
 mod theme;
 
 use clap::CommandFactory;
-use cli::{CacheCommands, Cli, Commands};
+use cli::{CacheCommands, Cli, Commands, ConfigCommands};
 use commands::{
-    handle_cache_clean, handle_completions, handle_list, handle_refresh, handle_status,
-    handle_theme_change,
+    handle_auto, handle_cache_clean, handle_cache_verify, handle_completions, handle_config_check,
+    handle_daemon, handle_history, handle_list, handle_pick, handle_redo, handle_refresh,
+    handle_show, handle_status, handle_theme_change, handle_undo, handle_watch,
 };
 use errors::Result;
 use log::error;
@@ -39,10 +44,16 @@ fn run() -> Result<()> {
     if let Some(ref command) = cli.command {
         match command {
             Commands::List { scheme, variants } => {
-                return handle_list(scheme.as_ref(), *variants);
+                return handle_list(scheme.as_ref(), *variants, cli.format);
             }
             Commands::Status => {
-                return handle_status();
+                return handle_status(cli.format);
+            }
+            Commands::Show { theme } => {
+                return handle_show(theme, cli.variant.as_deref(), cli.format);
+            }
+            Commands::Pick => {
+                return handle_pick(cli.scheme, cli.quiet);
             }
             Commands::Completions { shell } => {
                 return handle_completions(*shell);
@@ -50,11 +61,35 @@ fn run() -> Result<()> {
             Commands::Cache { command } => {
                 return match command {
                     CacheCommands::Clean => handle_cache_clean(),
+                    CacheCommands::Verify => handle_cache_verify(),
                 };
             }
             Commands::Refresh => {
                 return handle_refresh(cli.quiet);
             }
+            Commands::Auto { apply_now } => {
+                return handle_auto(*apply_now, cli.quiet);
+            }
+            Commands::Daemon => {
+                return handle_daemon();
+            }
+            Commands::Watch => {
+                return handle_watch();
+            }
+            Commands::History => {
+                return handle_history(cli.format);
+            }
+            Commands::Undo => {
+                return handle_undo(cli.quiet);
+            }
+            Commands::Redo => {
+                return handle_redo(cli.quiet);
+            }
+            Commands::Config { command } => {
+                return match command {
+                    ConfigCommands::Check => handle_config_check(cli.quiet),
+                };
+            }
         }
     }
 
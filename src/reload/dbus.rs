@@ -0,0 +1,262 @@
+//! D-Bus client for the `dbus` reload method
+//!
+//! Calls a method on the session bus, the system bus, or an explicit bus
+//! address (e.g. `unix:path=/run/user/1000/bus`), blocking until the reply
+//! arrives so failures are reported like any other reload method.
+
+use crate::config::DbusArg;
+use crate::errors::{Result, VogixError};
+use zbus::blocking::Connection;
+use zbus::zvariant::{StructureBuilder, Value};
+
+/// Bus used when the manifest doesn't set `dbus_bus`
+pub const DEFAULT_BUS: &str = "session";
+
+/// A fully resolved D-Bus method call
+pub struct MethodCall<'a> {
+    /// "session", "system", or a D-Bus address
+    pub bus: &'a str,
+    pub destination: &'a str,
+    pub path: &'a str,
+    pub interface: Option<&'a str>,
+    pub method: &'a str,
+    pub args: &'a [DbusArg],
+}
+
+/// Call the method and wait for its reply
+///
+/// Returns the reply body signature so callers can report what came back.
+pub fn call(call: &MethodCall<'_>) -> Result<String> {
+    let connection = connect(call.bus)?;
+
+    let reply = if call.args.is_empty() {
+        connection.call_method(
+            Some(call.destination),
+            call.path,
+            call.interface,
+            call.method,
+            &(),
+        )
+    } else {
+        let body = call
+            .args
+            .iter()
+            .fold(StructureBuilder::new(), |builder, arg| {
+                builder.append_field(to_dbus_value(arg))
+            })
+            .build()
+            .map_err(|e| VogixError::reload(format!("invalid dbus_args: {}", e)))?;
+        connection.call_method(
+            Some(call.destination),
+            call.path,
+            call.interface,
+            call.method,
+            &body,
+        )
+    }
+    .map_err(|e| {
+        VogixError::reload(format!(
+            "D-Bus call {} {}.{} failed: {}",
+            call.destination,
+            call.interface.unwrap_or("*"),
+            call.method,
+            e
+        ))
+    })?;
+
+    Ok(reply.body().signature().to_string())
+}
+
+/// Open a connection to the requested bus
+fn connect(bus: &str) -> Result<Connection> {
+    let connection = match bus {
+        "session" => Connection::session(),
+        "system" => Connection::system(),
+        address if address.contains(':') => {
+            zbus::blocking::connection::Builder::address(address).and_then(|b| b.build())
+        }
+        other => {
+            return Err(VogixError::reload(format!(
+                "unknown dbus_bus '{}' (expected session, system or a bus address)",
+                other
+            )));
+        }
+    };
+
+    connection.map_err(|e| VogixError::reload(format!("failed to connect to {} bus: {}", bus, e)))
+}
+
+/// Convert a typed manifest argument to a D-Bus value
+fn to_dbus_value(arg: &DbusArg) -> Value<'static> {
+    match arg {
+        DbusArg::String(s) => Value::from(s.clone()),
+        DbusArg::Int32(i) => Value::from(*i),
+        DbusArg::Int64(i) => Value::from(*i),
+        DbusArg::Bool(b) => Value::from(*b),
+        DbusArg::Double(f) => Value::from(*f),
+        DbusArg::StringArray(items) => Value::from(items.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, ErrorKind};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    /// A dbus-daemon private to one test, killed on drop
+    struct PrivateBus {
+        child: Child,
+        address: String,
+        _dir: TempDir,
+    }
+
+    impl PrivateBus {
+        /// Start a session-style bus, or None if dbus-daemon isn't installed
+        fn start() -> Option<Self> {
+            let dir = TempDir::new().unwrap();
+            let listen = format!("unix:path={}", dir.path().join("bus").display());
+            let child = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address=1"])
+                .arg(format!("--address={}", listen))
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    eprintln!("dbus-daemon not found, skipping");
+                    return None;
+                }
+                Err(e) => panic!("failed to start dbus-daemon: {}", e),
+            };
+
+            let mut address = String::new();
+            BufReader::new(child.stdout.take().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+
+            Some(Self {
+                child,
+                address: address.trim().to_string(),
+                _dir: dir,
+            })
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    /// Test service recording the arguments of every Reload call
+    struct Recorder {
+        calls: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    #[zbus::interface(name = "org.vogix.Test")]
+    impl Recorder {
+        fn reload(&self, paths: Vec<String>) {
+            self.calls.lock().unwrap().push(paths);
+        }
+
+        fn ping(&self) -> String {
+            "pong".to_string()
+        }
+    }
+
+    fn serve(bus: &PrivateBus) -> (Connection, Arc<Mutex<Vec<Vec<String>>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Recorder {
+            calls: Arc::clone(&calls),
+        };
+        let connection = zbus::blocking::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .name("org.vogix.Test")
+            .unwrap()
+            .serve_at("/org/vogix/Test", recorder)
+            .unwrap()
+            .build()
+            .unwrap();
+        (connection, calls)
+    }
+
+    fn method_call<'a>(
+        bus: &'a PrivateBus,
+        method: &'a str,
+        args: &'a [DbusArg],
+    ) -> MethodCall<'a> {
+        MethodCall {
+            bus: &bus.address,
+            destination: "org.vogix.Test",
+            path: "/org/vogix/Test",
+            interface: Some("org.vogix.Test"),
+            method,
+            args,
+        }
+    }
+
+    #[test]
+    fn test_call_passes_arguments() {
+        let Some(bus) = PrivateBus::start() else {
+            return;
+        };
+        let (_service, calls) = serve(&bus);
+
+        let args = vec![DbusArg::StringArray(vec![
+            "/home/user/.config/dunst/dunstrc".to_string(),
+        ])];
+        call(&method_call(&bus, "Reload", &args)).unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![vec!["/home/user/.config/dunst/dunstrc".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_call_without_arguments_reports_reply_signature() {
+        let Some(bus) = PrivateBus::start() else {
+            return;
+        };
+        let (_service, _calls) = serve(&bus);
+
+        let signature = call(&method_call(&bus, "Ping", &[])).unwrap();
+        assert_eq!(signature, "s");
+    }
+
+    #[test]
+    fn test_call_unknown_method_fails() {
+        let Some(bus) = PrivateBus::start() else {
+            return;
+        };
+        let (_service, _calls) = serve(&bus);
+
+        let result = call(&method_call(&bus, "Restart", &[]));
+        assert!(result.unwrap_err().to_string().contains("Restart"));
+    }
+
+    #[test]
+    fn test_connect_unknown_bus() {
+        let result = connect("user");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_dbus_value_types() {
+        assert_eq!(to_dbus_value(&DbusArg::Int32(5)), Value::from(5i32));
+        assert_eq!(
+            to_dbus_value(&DbusArg::Int64(i64::MAX)),
+            Value::from(i64::MAX)
+        );
+        assert_eq!(to_dbus_value(&DbusArg::Bool(true)), Value::from(true));
+        assert_eq!(
+            to_dbus_value(&DbusArg::StringArray(vec!["a".to_string()])),
+            Value::from(vec!["a".to_string()])
+        );
+    }
+}
@@ -0,0 +1,560 @@
+//! Application reload dispatching
+//!
+//! Notifies themed applications after a theme switch using the reload method
+//! declared for each app in the manifest (`signal`, `command`, `touch`,
+//! `dbus`, `sway-ipc` or `none`).
+//!
+//! # Module Structure
+//! - `process`: /proc scanning and kill(2) for the `signal` method
+//! - `dbus`: D-Bus method calls (requires the `dbus` cargo feature)
+//! - `sway`: Sway/i3 IPC client (requires the `sway` cargo feature)
+
+#[cfg(feature = "dbus")]
+mod dbus;
+mod process;
+#[cfg(feature = "sway")]
+mod sway;
+#[cfg(test)]
+mod tests;
+
+use crate::config::{AppMetadata, Config, DbusReload, ReloadMethod, SignalReload, SwayIpcReload};
+use crate::errors::{Result, VogixError};
+use log::debug;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Time an app may take to reload when the manifest doesn't set `reload_timeout`
+pub const DEFAULT_RELOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Extra time given to a reload thread past its timeout, so a killed
+/// `reload_command` can report its own outcome and captured output
+const TIMEOUT_GRACE: Duration = Duration::from_secs(1);
+
+/// How often a running `reload_command` is checked for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How a single app's reload ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadOutcome {
+    Ok,
+    Failed,
+    /// `reload_method = "none"`, nothing was attempted
+    Skipped,
+    /// Didn't finish within `reload_timeout`; a `reload_command` is killed
+    TimedOut,
+}
+
+impl fmt::Display for ReloadOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+            Self::TimedOut => "timed-out",
+        })
+    }
+}
+
+/// Per-app record of a reload attempt
+#[derive(Debug, Clone)]
+pub struct AppReloadReport {
+    pub app_name: String,
+    pub outcome: ReloadOutcome,
+    /// What was done, or why it failed
+    pub message: String,
+    /// Wall-clock time spent reloading the app
+    pub duration: Duration,
+    /// Captured output of `reload_command` (empty for other methods)
+    pub stdout: String,
+    pub stderr: String,
+    /// PIDs that received a reload signal (signal method only)
+    pub pids: Vec<u32>,
+}
+
+/// Result of reloading applications
+#[derive(Debug)]
+pub struct ReloadResult {
+    /// Number of apps successfully reloaded
+    pub success_count: usize,
+    /// Total apps that attempted reload (excludes apps with reload_method = "none")
+    pub total_count: usize,
+    /// Apps that failed or timed out, with error messages
+    pub failed_apps: Vec<(String, String)>,
+    /// One report per configured app, sorted by app name
+    pub apps: Vec<AppReloadReport>,
+}
+
+/// Outcome of a single-app reload, before timing is attached
+#[derive(Debug)]
+struct AppReload {
+    outcome: ReloadOutcome,
+    /// Human-readable description of what was done
+    message: String,
+    /// Processes signalled, if the reload method sends signals
+    pids: Vec<u32>,
+    stdout: String,
+    stderr: String,
+}
+
+impl AppReload {
+    fn message(message: impl Into<String>) -> Self {
+        Self::with_outcome(ReloadOutcome::Ok, message)
+    }
+
+    fn with_outcome(outcome: ReloadOutcome, message: impl Into<String>) -> Self {
+        Self {
+            outcome,
+            message: message.into(),
+            pids: Vec::new(),
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    }
+
+    fn into_report(self, app_name: String, duration: Duration) -> AppReloadReport {
+        AppReloadReport {
+            app_name,
+            outcome: self.outcome,
+            message: self.message,
+            duration,
+            stdout: self.stdout,
+            stderr: self.stderr,
+            pids: self.pids,
+        }
+    }
+}
+
+/// Exit status and output of a shell command run by [`run_shell`]
+#[derive(Debug)]
+pub struct CommandOutput {
+    /// None if the command was killed after timing out
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ReloadResult {
+    /// Returns true if any applications failed to reload
+    pub fn has_failures(&self) -> bool {
+        !self.failed_apps.is_empty()
+    }
+
+    /// Names of failed apps marked `critical` in `config`
+    pub fn critical_failures<'a>(&'a self, config: &Config) -> Vec<&'a str> {
+        self.failed_apps
+            .iter()
+            .filter(|(app_name, _)| config.apps.get(app_name).is_some_and(|app| app.critical))
+            .map(|(app_name, _)| app_name.as_str())
+            .collect()
+    }
+
+    /// Log per-app reload details at debug level
+    pub fn log_details(&self) {
+        for report in &self.apps {
+            debug!(
+                "  {}: {} in {:.2?} ({})",
+                report.app_name, report.outcome, report.duration, report.message
+            );
+            if !report.pids.is_empty() {
+                debug!("    signalled PIDs {:?}", report.pids);
+            }
+            for line in report.stdout.lines() {
+                debug!("    stdout: {}", line);
+            }
+            for line in report.stderr.lines() {
+                debug!("    stderr: {}", line);
+            }
+        }
+    }
+}
+
+pub struct ReloadDispatcher;
+
+impl ReloadDispatcher {
+    pub fn new() -> Self {
+        ReloadDispatcher
+    }
+
+    /// Reload all themed applications concurrently
+    /// Each app runs on its own thread and is abandoned once its
+    /// `reload_timeout` expires, so a hung app can't block the others.
+    /// Returns a ReloadResult with details about successes and failures.
+    /// When `quiet` is true, suppresses success messages (errors still go to stderr).
+    pub fn reload_apps(&self, config: &Config, quiet: bool) -> ReloadResult {
+        if config.apps.is_empty() {
+            if !quiet {
+                println!("No applications configured");
+            }
+            return ReloadResult {
+                success_count: 0,
+                total_count: 0,
+                failed_apps: Vec::new(),
+                apps: Vec::new(),
+            };
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let mut reports = Vec::with_capacity(config.apps.len());
+        // App name -> (start time, timeout) for every reload still running
+        let mut pending: HashMap<String, (Instant, Duration)> = HashMap::new();
+
+        for (app_name, app_metadata) in &config.apps {
+            // Skip apps that don't need reloading
+            if app_metadata.reload_method == ReloadMethod::None {
+                reports.push(
+                    AppReload::with_outcome(ReloadOutcome::Skipped, "no reload needed")
+                        .into_report(app_name.clone(), Duration::ZERO),
+                );
+                continue;
+            }
+
+            let timeout = app_metadata
+                .reload_timeout
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_RELOAD_TIMEOUT);
+            let started = Instant::now();
+            let sender = sender.clone();
+            let thread_app = app_name.clone();
+            let metadata = app_metadata.clone();
+
+            let spawned = thread::Builder::new()
+                .name(format!("reload-{}", app_name))
+                .spawn(move || {
+                    let reload = ReloadDispatcher.reload_app(&thread_app, &metadata, timeout);
+                    let _ = sender.send(reload.into_report(thread_app, started.elapsed()));
+                });
+
+            match spawned {
+                Ok(_) => {
+                    pending.insert(app_name.clone(), (started, timeout));
+                }
+                Err(e) => reports.push(
+                    AppReload::with_outcome(
+                        ReloadOutcome::Failed,
+                        format!("failed to start reload thread: {}", e),
+                    )
+                    .into_report(app_name.clone(), started.elapsed()),
+                ),
+            }
+        }
+        drop(sender);
+
+        while !pending.is_empty() {
+            let next_deadline = pending
+                .values()
+                .map(|(started, timeout)| *started + *timeout + TIMEOUT_GRACE)
+                .min()
+                .unwrap_or_else(Instant::now);
+
+            match receiver.recv_timeout(next_deadline.saturating_duration_since(Instant::now())) {
+                Ok(report) => {
+                    pending.remove(&report.app_name);
+                    reports.push(report);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let now = Instant::now();
+                    let expired: Vec<String> = pending
+                        .iter()
+                        .filter(|(_, (started, timeout))| {
+                            now >= *started + *timeout + TIMEOUT_GRACE
+                        })
+                        .map(|(app_name, _)| app_name.clone())
+                        .collect();
+                    for app_name in expired {
+                        let (started, timeout) =
+                            pending.remove(&app_name).unwrap_or((now, Duration::ZERO));
+                        reports.push(
+                            AppReload::with_outcome(
+                                ReloadOutcome::TimedOut,
+                                format!("no response after {}s", timeout.as_secs()),
+                            )
+                            .into_report(app_name, started.elapsed()),
+                        );
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    // Every sender is gone, so the remaining threads panicked
+                    for (app_name, (started, _)) in pending.drain() {
+                        reports.push(
+                            AppReload::with_outcome(
+                                ReloadOutcome::Failed,
+                                "reload thread panicked",
+                            )
+                            .into_report(app_name, started.elapsed()),
+                        );
+                    }
+                }
+            }
+        }
+
+        reports.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+
+        let failed_apps: Vec<(String, String)> = reports
+            .iter()
+            .filter(|report| {
+                matches!(
+                    report.outcome,
+                    ReloadOutcome::Failed | ReloadOutcome::TimedOut
+                )
+            })
+            .map(|report| (report.app_name.clone(), report.message.clone()))
+            .collect();
+        let total_count = reports
+            .iter()
+            .filter(|report| report.outcome != ReloadOutcome::Skipped)
+            .count();
+        let success_count = total_count - failed_apps.len();
+
+        print_summary(success_count, total_count, &failed_apps, quiet);
+
+        ReloadResult {
+            success_count,
+            total_count,
+            failed_apps,
+            apps: reports,
+        }
+    }
+
+    /// Reload a single application using metadata from manifest
+    ///
+    /// A `reload_command` still running after `timeout` is killed.
+    fn reload_app(&self, app_name: &str, metadata: &AppMetadata, timeout: Duration) -> AppReload {
+        self.try_reload_app(app_name, metadata, timeout)
+            .unwrap_or_else(|e| AppReload::with_outcome(ReloadOutcome::Failed, e.to_string()))
+    }
+
+    fn try_reload_app(
+        &self,
+        app_name: &str,
+        metadata: &AppMetadata,
+        timeout: Duration,
+    ) -> Result<AppReload> {
+        match &metadata.reload_method {
+            ReloadMethod::Signal(signal) => {
+                let process_name = signal.process_name.as_deref().unwrap_or(app_name);
+                let pids = self.send_signal(process_name, signal)?;
+                let message = format!(
+                    "sent {} signal to {} process(es)",
+                    signal.reload_signal,
+                    pids.len()
+                );
+                Ok(AppReload {
+                    pids,
+                    ..AppReload::message(message)
+                })
+            }
+            ReloadMethod::Command { reload_command } => {
+                let output = run_shell(reload_command, &[], timeout)?;
+                let (outcome, message) = match output.status {
+                    Some(status) if status.success() => {
+                        (ReloadOutcome::Ok, "executed reload command".to_string())
+                    }
+                    Some(status) => (
+                        ReloadOutcome::Failed,
+                        format!("command failed ({}): {}", status, output.stderr.trim()),
+                    ),
+                    None => (
+                        ReloadOutcome::TimedOut,
+                        format!("command killed after {}s", timeout.as_secs()),
+                    ),
+                };
+                Ok(AppReload {
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    ..AppReload::with_outcome(outcome, message)
+                })
+            }
+            ReloadMethod::Touch => {
+                // Touch the symlink itself (-h flag) to update its mtime
+                // Applications watching the file will detect the change
+                Command::new("touch")
+                    .arg("-h")
+                    .arg(&metadata.config_path)
+                    .status()
+                    .map_err(|e| {
+                        VogixError::reload_with_source("failed to touch config file", e)
+                    })?;
+                Ok(AppReload::message("touched to trigger auto-reload"))
+            }
+            ReloadMethod::Dbus(dbus) => self.dbus_call(dbus).map(AppReload::message),
+            ReloadMethod::SwayIpc(ipc) => self.sway_ipc(ipc).map(AppReload::message),
+            ReloadMethod::None => Ok(AppReload::message(
+                "no reload needed (changes take effect on next use)",
+            )),
+        }
+    }
+
+    /// Send a Unix signal to every matching process owned by the current user
+    /// Returns the PIDs that were signalled.
+    fn send_signal(&self, process_name: &str, signal: &SignalReload) -> Result<Vec<u32>> {
+        let signal_number = process::parse_signal(&signal.reload_signal)?;
+        let query = process::ProcessQuery::for_app(process_name, signal)?;
+
+        let pids = query.find(Path::new(process::PROC_ROOT));
+        if pids.is_empty() {
+            return Err(VogixError::reload(format!(
+                "process '{}' is not running",
+                process_name
+            )));
+        }
+
+        process::signal_all(&pids, signal_number)
+    }
+
+    /// Call the app's reload method over D-Bus
+    #[cfg(feature = "dbus")]
+    fn dbus_call(&self, dbus: &DbusReload) -> Result<String> {
+        let args = dbus.args().map_err(VogixError::reload)?;
+        let call = dbus::MethodCall {
+            bus: dbus.dbus_bus.as_deref().unwrap_or(dbus::DEFAULT_BUS),
+            destination: &dbus.dbus_destination,
+            path: &dbus.dbus_path,
+            interface: dbus.dbus_interface.as_deref(),
+            method: &dbus.dbus_method,
+            args: &args,
+        };
+        let reply_signature = dbus::call(&call)?;
+
+        Ok(format!(
+            "called {}.{} (reply: '{}')",
+            call.destination, call.method, reply_signature
+        ))
+    }
+
+    /// D-Bus is unavailable without the `dbus` cargo feature
+    #[cfg(not(feature = "dbus"))]
+    fn dbus_call(&self, _dbus: &DbusReload) -> Result<String> {
+        Err(VogixError::reload(
+            "dbus reload method requires vogix built with the `dbus` feature",
+        ))
+    }
+
+    /// Run compositor commands over the Sway/i3 IPC socket
+    ///
+    /// Runs each entry of `ipc_commands` (default: `reload`) and reports the
+    /// outcome of every command in the returned message.
+    #[cfg(feature = "sway")]
+    fn sway_ipc(&self, ipc: &SwayIpcReload) -> Result<String> {
+        let commands = match &ipc.ipc_commands {
+            Some(commands) if !commands.is_empty() => commands.clone(),
+            _ => vec![sway::DEFAULT_COMMAND.to_string()],
+        };
+        let outcomes = sway::run_commands(ipc.ipc_socket.as_deref(), &commands)?;
+        Ok(format!("ipc: {}", outcomes.join(", ")))
+    }
+
+    /// Sway/i3 IPC is unavailable without the `sway` cargo feature
+    #[cfg(not(feature = "sway"))]
+    fn sway_ipc(&self, _ipc: &SwayIpcReload) -> Result<String> {
+        Err(VogixError::reload(
+            "sway-ipc reload method requires vogix built with the `sway` feature",
+        ))
+    }
+}
+
+/// Print the outcome of reloading apps
+///
+/// The success line is suppressed when `quiet` is true; failures are always
+/// printed to stderr. Also used by the CLI for changes applied by the daemon.
+pub fn print_summary(
+    success_count: usize,
+    total_count: usize,
+    failed_apps: &[(String, String)],
+    quiet: bool,
+) {
+    if failed_apps.is_empty() {
+        if !quiet {
+            if total_count > 0 {
+                println!("✓ Reloaded {} applications", success_count);
+            } else {
+                println!("No applications needed reloading");
+            }
+        }
+    } else {
+        eprintln!(
+            "⚠ Reloaded {}/{} applications. Failures:",
+            success_count, total_count
+        );
+        for (app_name, error) in failed_apps {
+            eprintln!("  - {}: {}", app_name, error);
+        }
+    }
+}
+
+/// Run a shell command with extra environment variables, killing it if it outlives `timeout`
+///
+/// The command runs in its own process group so anything it spawned is
+/// killed with it. Output is captured on reader threads; a background
+/// process that keeps the pipes open past the deadline only loses output.
+pub fn run_shell(cmd: &str, env: &[(String, String)], timeout: Duration) -> Result<CommandOutput> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .map_err(|e| VogixError::reload_with_source("failed to run command", e))?;
+
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| VogixError::reload_with_source("failed to wait for command", e))?
+        {
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            if let Err(e) = process::signal_group(child.id(), libc::SIGKILL) {
+                debug!("{}", e);
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let output_deadline = deadline.max(Instant::now()) + POLL_INTERVAL;
+    let collect = |pipe: mpsc::Receiver<String>| {
+        pipe.recv_timeout(output_deadline.saturating_duration_since(Instant::now()))
+            .unwrap_or_default()
+    };
+
+    Ok(CommandOutput {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+/// Read a child's pipe to the end on a separate thread
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(mut pipe) = pipe {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            let _ = sender.send(String::from_utf8_lossy(&buffer).into_owned());
+        });
+    }
+    receiver
+}
+
+impl Default for ReloadDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,449 @@
+//! In-process signal delivery for the `signal` reload method
+//!
+//! Scans `/proc` for processes matching an app's `process_name` and signals
+//! them with kill(2), so reloading works without procps/psmisc and never
+//! touches processes owned by other users.
+//!
+//! Matching (`process_match`):
+//! - `comm` (default): the kernel task name, truncated to 15 bytes like `/proc/PID/comm`
+//! - `exe`: basename of the `/proc/PID/exe` symlink
+//! - `cmdline`: regex over the full command line, arguments joined by spaces
+//!
+//! Optional filters: `process_cgroup` (substring of `/proc/PID/cgroup`) and
+//! `process_same_session` (same audit session as vogix itself).
+
+use crate::config::{ProcessMatch, SignalReload};
+use crate::errors::{Result, VogixError};
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Root of the live process filesystem
+pub const PROC_ROOT: &str = "/proc";
+
+/// Maximum length of `/proc/PID/comm` (TASK_COMM_LEN - 1)
+const COMM_MAX_LEN: usize = 15;
+
+/// How `process_name` is compared against a process
+#[derive(Debug)]
+enum MatchBy {
+    Comm(String),
+    Exe(String),
+    Cmdline(Regex),
+}
+
+/// Criteria for selecting processes to signal
+#[derive(Debug)]
+pub struct ProcessQuery {
+    match_by: MatchBy,
+    /// Only processes with this real UID
+    uid: Option<u32>,
+    /// Only processes whose cgroup path contains this string
+    cgroup: Option<String>,
+    /// Only processes in this audit session
+    session: Option<String>,
+}
+
+impl ProcessQuery {
+    /// Build the query for an app, scoped to the current user
+    pub fn for_app(process_name: &str, signal: &SignalReload) -> Result<Self> {
+        let match_by = match signal.process_match {
+            ProcessMatch::Comm => MatchBy::Comm(truncate_comm(process_name).to_string()),
+            ProcessMatch::Exe => MatchBy::Exe(process_name.to_string()),
+            ProcessMatch::Cmdline => {
+                MatchBy::Cmdline(Regex::new(process_name).map_err(|e| {
+                    VogixError::reload(format!("invalid process_name regex: {}", e))
+                })?)
+            }
+        };
+
+        let session = if signal.process_same_session {
+            Some(
+                read_trimmed(&Path::new(PROC_ROOT).join("self/sessionid")).map_err(|e| {
+                    VogixError::reload_with_source("failed to read own session id", e)
+                })?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            match_by,
+            uid: Some(current_uid()),
+            cgroup: signal.process_cgroup.clone(),
+            session,
+        })
+    }
+
+    /// Find the PIDs of all matching processes under `proc_root`, sorted ascending
+    ///
+    /// Processes that exit or can't be inspected during the scan are skipped.
+    pub fn find(&self, proc_root: &Path) -> Vec<u32> {
+        let Ok(entries) = fs::read_dir(proc_root) else {
+            return Vec::new();
+        };
+
+        let mut pids: Vec<u32> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+                self.matches(&entry.path()).then_some(pid)
+            })
+            .collect();
+
+        pids.sort_unstable();
+        pids
+    }
+
+    /// Check a single `/proc/PID` directory against every criterion
+    fn matches(&self, proc_dir: &Path) -> bool {
+        if let Some(uid) = self.uid
+            && read_real_uid(proc_dir) != Some(uid)
+        {
+            return false;
+        }
+
+        let name_matches = match &self.match_by {
+            MatchBy::Comm(name) => {
+                read_trimmed(&proc_dir.join("comm")).is_ok_and(|comm| &comm == name)
+            }
+            MatchBy::Exe(name) => fs::read_link(proc_dir.join("exe")).is_ok_and(|exe| {
+                exe.file_name()
+                    .is_some_and(|base| base.to_string_lossy() == *name)
+            }),
+            MatchBy::Cmdline(regex) => {
+                read_cmdline(proc_dir).is_some_and(|cmd| regex.is_match(&cmd))
+            }
+        };
+        if !name_matches {
+            return false;
+        }
+
+        if let Some(cgroup) = &self.cgroup
+            && !fs::read_to_string(proc_dir.join("cgroup"))
+                .is_ok_and(|c| c.contains(cgroup.as_str()))
+        {
+            return false;
+        }
+
+        if let Some(session) = &self.session
+            && read_trimmed(&proc_dir.join("sessionid")).ok().as_ref() != Some(session)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Parse a signal name ("USR1", "SIGHUP") or number ("10") into a signal number
+///
+/// Numbers must name a real signal, from 1 to `SIGRTMAX`; 0 would only probe
+/// whether the processes exist.
+pub fn parse_signal(signal: &str) -> Result<libc::c_int> {
+    if let Ok(number) = signal.parse::<libc::c_int>() {
+        if !(1..=libc::SIGRTMAX()).contains(&number) {
+            return Err(VogixError::reload(format!("unknown signal: {}", signal)));
+        }
+        return Ok(number);
+    }
+
+    let upper = signal.to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    let number = match name {
+        "HUP" => libc::SIGHUP,
+        "INT" => libc::SIGINT,
+        "QUIT" => libc::SIGQUIT,
+        "KILL" => libc::SIGKILL,
+        "USR1" => libc::SIGUSR1,
+        "USR2" => libc::SIGUSR2,
+        "ALRM" => libc::SIGALRM,
+        "TERM" => libc::SIGTERM,
+        "CONT" => libc::SIGCONT,
+        "STOP" => libc::SIGSTOP,
+        "WINCH" => libc::SIGWINCH,
+        _ => {
+            return Err(VogixError::reload(format!("unknown signal: {}", signal)));
+        }
+    };
+    Ok(number)
+}
+
+/// Deliver a signal to each PID with kill(2)
+///
+/// Returns the PIDs that received it. Processes that exited since the scan
+/// are ignored; it's an error only if no process could be signalled.
+pub fn signal_all(pids: &[u32], signal: libc::c_int) -> Result<Vec<u32>> {
+    let mut delivered = Vec::with_capacity(pids.len());
+    let mut last_error = None;
+
+    for &pid in pids {
+        let Ok(raw_pid) = libc::pid_t::try_from(pid) else {
+            continue;
+        };
+        // SAFETY: kill(2) has no memory-safety preconditions; a positive pid
+        // targets exactly one process.
+        if unsafe { libc::kill(raw_pid, signal) } == 0 {
+            delivered.push(pid);
+        } else {
+            last_error = Some(io::Error::last_os_error());
+        }
+    }
+
+    match (delivered.is_empty(), last_error) {
+        (true, Some(e)) => Err(VogixError::reload_with_source("failed to send signal", e)),
+        _ => Ok(delivered),
+    }
+}
+
+/// Send a signal to every process in a process group
+pub fn signal_group(pgid: u32, signal: libc::c_int) -> Result<()> {
+    let raw_pgid = libc::pid_t::try_from(pgid)
+        .map_err(|_| VogixError::reload(format!("invalid process group {}", pgid)))?;
+    // SAFETY: kill(2) has no memory-safety preconditions; a negative pid
+    // targets the process group, never the caller's own (pgid is a child's).
+    if unsafe { libc::kill(-raw_pgid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(VogixError::reload_with_source(
+            "failed to signal process group",
+            io::Error::last_os_error(),
+        ))
+    }
+}
+
+/// Real UID of the calling process
+fn current_uid() -> u32 {
+    // SAFETY: getuid(2) always succeeds and has no preconditions.
+    unsafe { libc::getuid() }
+}
+
+/// Names longer than the kernel's comm limit only ever match truncated
+fn truncate_comm(name: &str) -> &str {
+    let mut end = name.len().min(COMM_MAX_LEN);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+fn read_trimmed(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map(|s| s.trim_end().to_string())
+}
+
+/// Real UID from the `Uid:` line of `/proc/PID/status`
+fn read_real_uid(proc_dir: &Path) -> Option<u32> {
+    let status = fs::read_to_string(proc_dir.join("status")).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// NUL-separated `/proc/PID/cmdline` joined with spaces (None for kernel threads)
+fn read_cmdline(proc_dir: &Path) -> Option<String> {
+    let raw = fs::read(proc_dir.join("cmdline")).ok()?;
+    if raw.is_empty() {
+        return None;
+    }
+    let args: Vec<String> = raw
+        .split(|&b| b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    Some(args.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{Child, Command, Stdio};
+    use tempfile::TempDir;
+
+    /// Write a fake /proc/PID entry
+    fn fake_process(root: &Path, pid: u32, comm: &str, uid: u32, cmdline: &[&str], cgroup: &str) {
+        let dir = root.join(pid.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("comm"), format!("{}\n", comm)).unwrap();
+        fs::write(
+            dir.join("status"),
+            format!(
+                "Name:\t{}\nUid:\t{}\t{}\t{}\t{}\n",
+                comm, uid, uid, uid, uid
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("cmdline"), format!("{}\0", cmdline.join("\0"))).unwrap();
+        fs::write(dir.join("cgroup"), format!("0::{}\n", cgroup)).unwrap();
+        fs::write(dir.join("sessionid"), "3").unwrap();
+        std::os::unix::fs::symlink(format!("/usr/bin/{}", cmdline[0]), dir.join("exe")).unwrap();
+    }
+
+    fn query(match_by: MatchBy) -> ProcessQuery {
+        ProcessQuery {
+            match_by,
+            uid: Some(1000),
+            cgroup: None,
+            session: None,
+        }
+    }
+
+    fn fake_proc() -> TempDir {
+        let root = TempDir::new().unwrap();
+        let sway_slice = "/user.slice/user-1000.slice/session-3.scope";
+        fake_process(
+            root.path(),
+            100,
+            "waybar",
+            1000,
+            &["waybar", "-c", "top.json"],
+            sway_slice,
+        );
+        fake_process(
+            root.path(),
+            200,
+            "waybar",
+            1000,
+            &["waybar", "-c", "bottom.json"],
+            "/app.slice",
+        );
+        fake_process(root.path(), 300, "waybar", 0, &["waybar"], sway_slice);
+        fake_process(
+            root.path(),
+            400,
+            ".kitty-wrapped",
+            1000,
+            &["kitty"],
+            sway_slice,
+        );
+        fs::create_dir_all(root.path().join("self")).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_find_by_comm_filters_uid() {
+        let root = fake_proc();
+        let pids = query(MatchBy::Comm("waybar".to_string())).find(root.path());
+        assert_eq!(pids, vec![100, 200]);
+    }
+
+    #[test]
+    fn test_find_by_exe_basename() {
+        let root = fake_proc();
+        let pids = query(MatchBy::Exe("kitty".to_string())).find(root.path());
+        assert_eq!(pids, vec![400]);
+    }
+
+    #[test]
+    fn test_find_by_cmdline_regex() {
+        let root = fake_proc();
+        let regex = Regex::new(r"^waybar .*bottom\.json$").unwrap();
+        let pids = query(MatchBy::Cmdline(regex)).find(root.path());
+        assert_eq!(pids, vec![200]);
+    }
+
+    #[test]
+    fn test_find_filters_cgroup_and_session() {
+        let root = fake_proc();
+        let mut q = query(MatchBy::Comm("waybar".to_string()));
+        q.cgroup = Some("session-3.scope".to_string());
+        assert_eq!(q.find(root.path()), vec![100]);
+
+        q.cgroup = None;
+        q.session = Some("4".to_string());
+        assert!(q.find(root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_find_missing_proc_root() {
+        let q = query(MatchBy::Comm("waybar".to_string()));
+        assert!(q.find(Path::new("/nonexistent/proc")).is_empty());
+    }
+
+    #[test]
+    fn test_for_app_rejects_invalid_regex() {
+        let signal = SignalReload {
+            process_match: ProcessMatch::Cmdline,
+            ..Default::default()
+        };
+        assert!(ProcessQuery::for_app("waybar(", &signal).is_err());
+    }
+
+    #[test]
+    fn test_truncate_comm() {
+        assert_eq!(truncate_comm("btop"), "btop");
+        assert_eq!(
+            truncate_comm("gnome-shell-calendar-server"),
+            "gnome-shell-cal"
+        );
+    }
+
+    #[test]
+    fn test_parse_signal() {
+        assert_eq!(parse_signal("USR1").unwrap(), libc::SIGUSR1);
+        assert_eq!(parse_signal("SIGHUP").unwrap(), libc::SIGHUP);
+        assert_eq!(parse_signal("usr2").unwrap(), libc::SIGUSR2);
+        assert_eq!(parse_signal("15").unwrap(), 15);
+        assert!(parse_signal("RELOAD").is_err());
+    }
+
+    #[test]
+    fn test_parse_signal_rejects_out_of_range_numbers() {
+        assert_eq!(parse_signal("1").unwrap(), libc::SIGHUP);
+        assert_eq!(
+            parse_signal(&libc::SIGRTMAX().to_string()).unwrap(),
+            libc::SIGRTMAX()
+        );
+        for invalid in ["0", "-1", "-15", &(libc::SIGRTMAX() + 1).to_string()] {
+            let err = parse_signal(invalid).unwrap_err();
+            assert!(err.to_string().contains("unknown signal"), "{}", invalid);
+        }
+    }
+
+    /// Spawn `sleep <marker>` and wait until it has exec'd, so its
+    /// cmdline is no longer the forked test binary's
+    fn spawn_sleeper(marker: &str) -> Child {
+        let child = Command::new("sleep")
+            .arg(marker)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let proc_dir = Path::new(PROC_ROOT).join(child.id().to_string());
+        for _ in 0..200 {
+            if read_cmdline(&proc_dir).is_some_and(|cmd| cmd.ends_with(marker)) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        child
+    }
+
+    #[test]
+    fn test_signal_live_process() {
+        // Unique argument so no unrelated process matches
+        let marker = format!("{}.{}", 3600, std::process::id());
+        let mut child = spawn_sleeper(&marker);
+
+        let signal = SignalReload {
+            process_match: ProcessMatch::Cmdline,
+            ..Default::default()
+        };
+        let pattern = format!("^sleep {}$", regex::escape(&marker));
+        let q = ProcessQuery::for_app(&pattern, &signal).unwrap();
+        let pids = q.find(Path::new(PROC_ROOT));
+        if pids != vec![child.id()] {
+            let _ = child.kill();
+            panic!("expected [{}], found {:?}", child.id(), pids);
+        }
+
+        let delivered = signal_all(&pids, libc::SIGTERM).unwrap();
+        assert_eq!(delivered, vec![child.id()]);
+        assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGTERM));
+    }
+}
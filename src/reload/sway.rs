@@ -0,0 +1,140 @@
+//! Sway/i3 IPC client for the `sway-ipc` reload method
+//!
+//! Connects to the compositor socket and runs commands using the i3-ipc
+//! `RUN_COMMAND` message. The socket is taken from the manifest (`ipc_socket`)
+//! or discovered via `$I3SOCK` / `$SWAYSOCK`.
+
+use crate::errors::{Result, VogixError};
+use std::os::unix::net::UnixStream;
+use swayipc::Connection;
+
+/// Command run when the manifest doesn't list any `ipc_commands`
+pub const DEFAULT_COMMAND: &str = "reload";
+
+/// Run each command over the compositor IPC socket
+///
+/// Returns one `"{command}: ok"` entry per command. A single command may
+/// contain several sway commands separated by `;` or `,`, all of which
+/// must succeed.
+pub fn run_commands(socket: Option<&str>, commands: &[String]) -> Result<Vec<String>> {
+    let mut connection = connect(socket)?;
+    let mut outcomes = Vec::with_capacity(commands.len());
+
+    for command in commands {
+        let replies = connection
+            .run_command(command)
+            .map_err(|e| VogixError::reload(format!("ipc command '{}' failed: {}", command, e)))?;
+
+        let errors: Vec<String> = replies
+            .into_iter()
+            .filter_map(|reply| reply.err().map(|e| e.to_string()))
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(VogixError::reload(format!(
+                "ipc command '{}' failed: {}",
+                command,
+                errors.join("; ")
+            )));
+        }
+
+        outcomes.push(format!("{}: ok", command));
+    }
+
+    Ok(outcomes)
+}
+
+/// Connect to an explicit socket path, or let swayipc discover it
+fn connect(socket: Option<&str>) -> Result<Connection> {
+    match socket {
+        Some(path) => UnixStream::connect(path)
+            .map(Connection::from)
+            .map_err(|e| {
+                VogixError::reload_with_source(format!("failed to connect to {}", path), e)
+            }),
+        None => Connection::new()
+            .map_err(|e| VogixError::reload(format!("failed to connect to compositor IPC: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::path::Path;
+    use std::thread::{self, JoinHandle};
+    use tempfile::TempDir;
+
+    const MAGIC: &[u8; 6] = b"i3-ipc";
+    const RUN_COMMAND: u32 = 0;
+
+    /// Minimal i3-ipc server: answers every RUN_COMMAND with `reply`
+    /// and returns the payloads it received once the client disconnects.
+    fn fake_server(path: &Path, reply: &'static str) -> JoinHandle<Vec<String>> {
+        let listener = UnixListener::bind(path).unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            let mut header = [0u8; 14];
+
+            while stream.read_exact(&mut header).is_ok() {
+                assert_eq!(&header[..6], MAGIC);
+                let len = u32::from_ne_bytes(header[6..10].try_into().unwrap());
+                let msg_type = u32::from_ne_bytes(header[10..14].try_into().unwrap());
+                assert_eq!(msg_type, RUN_COMMAND);
+
+                let mut payload = vec![0u8; len as usize];
+                stream.read_exact(&mut payload).unwrap();
+                received.push(String::from_utf8(payload).unwrap());
+
+                let mut response = MAGIC.to_vec();
+                response.extend_from_slice(&(reply.len() as u32).to_ne_bytes());
+                response.extend_from_slice(&RUN_COMMAND.to_ne_bytes());
+                response.extend_from_slice(reply.as_bytes());
+                stream.write_all(&response).unwrap();
+            }
+
+            received
+        })
+    }
+
+    #[test]
+    fn test_run_commands_sends_each_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("sway.sock");
+        let server = fake_server(&socket, r#"[{"success":true}]"#);
+
+        let commands = vec!["reload".to_string(), "bar mode dock".to_string()];
+        let outcomes = run_commands(socket.to_str(), &commands).unwrap();
+
+        assert_eq!(outcomes, vec!["reload: ok", "bar mode dock: ok"]);
+        assert_eq!(server.join().unwrap(), commands);
+    }
+
+    #[test]
+    fn test_run_commands_reports_failure_payload() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("sway.sock");
+        let server = fake_server(
+            &socket,
+            r#"[{"success":false,"parse_error":true,"error":"Unknown command 'relaod'"}]"#,
+        );
+
+        let result = run_commands(socket.to_str(), &["relaod".to_string()]);
+
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("relaod"));
+        assert!(err_msg.contains("Unknown command"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_run_commands_missing_socket() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("missing.sock");
+
+        let result = run_commands(socket.to_str(), &["reload".to_string()]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Tests for reload module
+
+use super::*;
+use crate::config::{AppMetadata, ProcessMatch, SignalReload};
+use std::collections::HashMap;
+
+#[test]
+fn test_reload_dispatcher_creation() {
+    // Verify it can be created
+    let _dispatcher = ReloadDispatcher::new();
+}
+
+#[test]
+fn test_reload_app_with_touch_method() {
+    let dispatcher = ReloadDispatcher::new();
+    let metadata = AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::Touch,
+        ..Default::default()
+    };
+
+    // Test that touch method doesn't crash
+    let reload = dispatcher.reload_app("test", &metadata, DEFAULT_RELOAD_TIMEOUT);
+    // Touch might fail if /tmp doesn't exist in test environment, that's OK
+    if reload.outcome == ReloadOutcome::Ok {
+        assert!(reload.message.contains("touched"));
+    }
+}
+
+#[test]
+fn test_reload_app_with_none_method() {
+    let dispatcher = ReloadDispatcher::new();
+    let metadata = AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::None,
+        ..Default::default()
+    };
+
+    let reload = dispatcher.reload_app("test", &metadata, DEFAULT_RELOAD_TIMEOUT);
+    assert_eq!(reload.outcome, ReloadOutcome::Ok);
+    assert!(reload.message.contains("no reload needed"));
+}
+
+#[test]
+fn test_reload_apps_returns_failure_count() {
+    let dispatcher = ReloadDispatcher::new();
+
+    // Create config with a command that will fail
+    let mut apps = HashMap::new();
+    apps.insert(
+        "failing_app".to_string(),
+        AppMetadata {
+            config_path: "/tmp/test.conf".to_string(),
+            reload_method: ReloadMethod::Command {
+                reload_command: "exit 1".to_string(), // This will fail
+            },
+            ..Default::default()
+        },
+    );
+    apps.insert(
+        "skipped_app".to_string(),
+        AppMetadata {
+            config_path: "/tmp/test.conf".to_string(),
+            reload_method: ReloadMethod::None,
+            ..Default::default()
+        },
+    );
+
+    let config = Config {
+        default_theme: "test".to_string(),
+        default_variant: "dark".to_string(),
+        apps,
+        templates: None,
+        theme_sources: None,
+        auto: None,
+        hooks: None,
+    };
+
+    let result = dispatcher.reload_apps(&config, false);
+
+    // The result should indicate there was a failure
+    assert!(
+        result.has_failures(),
+        "reload_apps should report failures when apps fail to reload"
+    );
+}
+
+#[test]
+fn test_critical_failures() {
+    let app = |reload_command: &str, critical| AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::Command {
+            reload_command: reload_command.to_string(),
+        },
+        critical,
+        ..Default::default()
+    };
+    let config = Config {
+        apps: HashMap::from([
+            ("compositor".to_string(), app("exit 1", true)),
+            ("bar".to_string(), app("exit 1", false)),
+            ("terminal".to_string(), app("true", true)),
+        ]),
+        ..Config::default()
+    };
+
+    let result = ReloadDispatcher::new().reload_apps(&config, true);
+
+    assert_eq!(result.failed_apps.len(), 2);
+    assert_eq!(result.critical_failures(&config), vec!["compositor"]);
+}
+
+#[cfg(not(feature = "sway"))]
+#[test]
+fn test_reload_app_sway_ipc_requires_feature() {
+    let dispatcher = ReloadDispatcher::new();
+    let metadata = AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::SwayIpc(Default::default()),
+        ..Default::default()
+    };
+
+    let reload = dispatcher.reload_app("sway", &metadata, DEFAULT_RELOAD_TIMEOUT);
+    assert_eq!(reload.outcome, ReloadOutcome::Failed);
+    let err_msg = reload.message;
+    assert!(err_msg.contains("`sway` feature"));
+}
+
+#[cfg(feature = "dbus")]
+#[test]
+fn test_reload_app_dbus_unreachable_bus() {
+    let dispatcher = ReloadDispatcher::new();
+    let metadata = AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::Dbus(crate::config::DbusReload {
+            dbus_bus: Some("unix:path=/nonexistent/vogix-test-bus".to_string()),
+            dbus_destination: "org.vogix.Test".to_string(),
+            dbus_path: "/org/vogix/Test".to_string(),
+            dbus_method: "Reload".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let reload = dispatcher.reload_app("mako", &metadata, DEFAULT_RELOAD_TIMEOUT);
+    assert_eq!(reload.outcome, ReloadOutcome::Failed);
+    let err_msg = reload.message;
+    assert!(err_msg.contains("failed to connect"));
+}
+
+#[test]
+fn test_reload_app_signal_process_not_running() {
+    let dispatcher = ReloadDispatcher::new();
+    let metadata = AppMetadata {
+        config_path: "/tmp/test.conf".to_string(),
+        reload_method: ReloadMethod::Signal(SignalReload {
+            reload_signal: "USR1".to_string(),
+            process_name: Some("vogix-test-no-such-process".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let reload = dispatcher.reload_app("missing", &metadata, DEFAULT_RELOAD_TIMEOUT);
+    assert_eq!(reload.outcome, ReloadOutcome::Failed);
+    let err_msg = reload.message;
+    assert!(err_msg.contains("not running"));
+}
+
+#[test]
+fn test_reload_apps_reports_signalled_pids() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let marker = format!("{}.{}", 7200, std::process::id());
+    let mut child = Command::new("sleep")
+        .arg(&marker)
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // Wait for exec so the child's cmdline is `sleep <marker>`
+    let cmdline_path = format!("/proc/{}/cmdline", child.id());
+    for _ in 0..200 {
+        if std::fs::read(&cmdline_path).is_ok_and(|raw| raw.starts_with(b"sleep\0")) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let mut apps = HashMap::new();
+    apps.insert(
+        "sleeper".to_string(),
+        AppMetadata {
+            config_path: "/tmp/test.conf".to_string(),
+            reload_method: ReloadMethod::Signal(SignalReload {
+                reload_signal: "TERM".to_string(),
+                process_name: Some(format!("^sleep {}$", regex::escape(&marker))),
+                process_match: ProcessMatch::Cmdline,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    let config = Config {
+        apps,
+        ..Config::default()
+    };
+
+    let result = ReloadDispatcher::new().reload_apps(&config, true);
+    if result.has_failures() {
+        let _ = child.kill();
+    }
+
+    assert!(!result.has_failures());
+    assert_eq!(result.apps[0].pids, vec![child.id()]);
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGTERM));
+}
+
+/// Config with one `command` app per (name, command, timeout)
+fn command_config(commands: &[(&str, &str, Option<u64>)]) -> Config {
+    let apps = commands
+        .iter()
+        .map(|(name, command, timeout)| {
+            (
+                name.to_string(),
+                AppMetadata {
+                    config_path: "/tmp/test.conf".to_string(),
+                    reload_method: ReloadMethod::Command {
+                        reload_command: command.to_string(),
+                    },
+                    reload_timeout: *timeout,
+                    critical: false,
+                },
+            )
+        })
+        .collect();
+    Config {
+        apps,
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_reload_apps_records_outcomes_and_output() {
+    let mut config = command_config(&[
+        ("echo", "echo themed; echo warning >&2", None),
+        ("broken", "echo boom >&2; exit 3", None),
+    ]);
+    config.apps.insert(
+        "static".to_string(),
+        AppMetadata {
+            config_path: "/tmp/test.conf".to_string(),
+            reload_method: ReloadMethod::None,
+            ..Default::default()
+        },
+    );
+
+    let result = ReloadDispatcher::new().reload_apps(&config, true);
+
+    let names: Vec<&str> = result.apps.iter().map(|r| r.app_name.as_str()).collect();
+    assert_eq!(names, vec!["broken", "echo", "static"]);
+    assert_eq!(result.total_count, 2);
+    assert_eq!(result.success_count, 1);
+
+    let broken = &result.apps[0];
+    assert_eq!(broken.outcome, ReloadOutcome::Failed);
+    assert_eq!(broken.stderr, "boom\n");
+    assert!(broken.message.contains("boom"));
+
+    let echo = &result.apps[1];
+    assert_eq!(echo.outcome, ReloadOutcome::Ok);
+    assert_eq!(echo.stdout, "themed\n");
+    assert_eq!(echo.stderr, "warning\n");
+
+    assert_eq!(result.apps[2].outcome, ReloadOutcome::Skipped);
+}
+
+#[test]
+fn test_reload_apps_kills_command_after_timeout() {
+    let config = command_config(&[
+        ("hung", "echo started; exec sleep 30", Some(1)),
+        ("quick", "true", None),
+    ]);
+
+    let started = Instant::now();
+    let result = ReloadDispatcher::new().reload_apps(&config, true);
+
+    // The hung command is killed at its timeout rather than after 30s
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(result.failed_apps.len(), 1);
+
+    let hung = &result.apps[0];
+    assert_eq!(hung.outcome, ReloadOutcome::TimedOut);
+    assert_eq!(hung.stdout, "started\n");
+    assert!(hung.duration >= Duration::from_secs(1));
+
+    assert_eq!(result.apps[1].outcome, ReloadOutcome::Ok);
+}
+
+#[test]
+fn test_reload_apps_runs_concurrently() {
+    let config = command_config(&[
+        ("a", "sleep 1", None),
+        ("b", "sleep 1", None),
+        ("c", "sleep 1", None),
+    ]);
+
+    let started = Instant::now();
+    let result = ReloadDispatcher::new().reload_apps(&config, true);
+
+    assert!(!result.has_failures());
+    assert!(started.elapsed() < Duration::from_millis(2500));
+}
+
+#[test]
+fn test_reload_outcome_display() {
+    assert_eq!(ReloadOutcome::Ok.to_string(), "ok");
+    assert_eq!(ReloadOutcome::TimedOut.to_string(), "timed-out");
+}
@@ -0,0 +1,274 @@
+//! Polarity schedule for automatic light/dark switching.
+//!
+//! Resolves the `[auto]` manifest section into either fixed local times or a
+//! location whose solar elevation decides between light and dark.
+
+use crate::config::AutoConfig;
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use std::fmt;
+
+/// Sun elevation at sunrise/sunset, accounting for refraction and the solar disc
+const HORIZON_DEGREES: f64 = -0.833;
+
+/// When light and dark variants should be active
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    /// Fixed local times
+    Times { light: NaiveTime, dark: NaiveTime },
+    /// Sunrise/sunset at a location
+    Solar { latitude: f64, longitude: f64 },
+}
+
+impl Schedule {
+    /// Build a schedule from the [auto] section
+    ///
+    /// Coordinates win over times when both are configured.
+    pub fn from_config(auto: &AutoConfig) -> Result<Self, String> {
+        match (auto.latitude, auto.longitude, &auto.light, &auto.dark) {
+            (Some(latitude), Some(longitude), _, _) => {
+                if !(-90.0..=90.0).contains(&latitude) {
+                    return Err(format!("latitude {} is outside -90..90", latitude));
+                }
+                if !(-180.0..=180.0).contains(&longitude) {
+                    return Err(format!("longitude {} is outside -180..180", longitude));
+                }
+                Ok(Schedule::Solar {
+                    latitude,
+                    longitude,
+                })
+            }
+            (Some(_), None, _, _) | (None, Some(_), _, _) => {
+                Err("latitude and longitude must be set together".to_string())
+            }
+            (None, None, Some(light), Some(dark)) => {
+                let light = parse_time("light", light)?;
+                let dark = parse_time("dark", dark)?;
+                if light == dark {
+                    return Err("light and dark times must differ".to_string());
+                }
+                Ok(Schedule::Times { light, dark })
+            }
+            (None, None, _, _) => {
+                Err("set either light and dark times or latitude and longitude".to_string())
+            }
+        }
+    }
+
+    /// Polarity ("light" or "dark") that should be active at `now`
+    ///
+    /// Times are compared in `now`'s timezone, so pass local time.
+    pub fn polarity_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> &'static str {
+        let is_light = match *self {
+            Schedule::Times { light, dark } => {
+                let time = now.time();
+                if light < dark {
+                    time >= light && time < dark
+                } else {
+                    time >= light || time < dark
+                }
+            }
+            Schedule::Solar {
+                latitude,
+                longitude,
+            } => sun_elevation(&now.with_timezone(&Utc), latitude, longitude) > HORIZON_DEGREES,
+        };
+        if is_light { "light" } else { "dark" }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Times { light, dark } => write!(
+                f,
+                "light at {}, dark at {}",
+                light.format("%H:%M"),
+                dark.format("%H:%M")
+            ),
+            Schedule::Solar {
+                latitude,
+                longitude,
+            } => write!(f, "sunrise/sunset at {:.2}, {:.2}", latitude, longitude),
+        }
+    }
+}
+
+fn parse_time(key: &str, value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| format!("{} = \"{}\" is not a HH:MM time", key, value))
+}
+
+/// Solar elevation in degrees above the horizon
+///
+/// Low-precision solar position (about 0.01° over 1950-2050), which is far
+/// more than enough to place sunrise and sunset within a minute.
+pub fn sun_elevation(now: &DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    // Days since J2000.0
+    let n = now.timestamp() as f64 / 86400.0 + 2440587.5 - 2451545.0;
+
+    let mean_longitude = (280.460 + 0.9856474 * n).rem_euclid(360.0);
+    let mean_anomaly = (357.528 + 0.9856003 * n).rem_euclid(360.0).to_radians();
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let obliquity = (23.439 - 0.0000004 * n).to_radians();
+
+    let declination = (obliquity.sin() * ecliptic_longitude.sin()).asin();
+    let right_ascension = (obliquity.cos() * ecliptic_longitude.sin())
+        .atan2(ecliptic_longitude.cos())
+        .to_degrees();
+
+    let sidereal_hours = (18.697374558 + 24.06570982441908 * n).rem_euclid(24.0);
+    let hour_angle = (sidereal_hours * 15.0 + longitude - right_ascension).to_radians();
+
+    let latitude = latitude.to_radians();
+    (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos())
+        .asin()
+        .to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn times(light: &str, dark: &str) -> Schedule {
+        Schedule::from_config(&AutoConfig {
+            light: Some(light.to_string()),
+            dark: Some(dark.to_string()),
+            ..AutoConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn solar(latitude: f64, longitude: f64) -> Schedule {
+        Schedule::from_config(&AutoConfig {
+            latitude: Some(latitude),
+            longitude: Some(longitude),
+            ..AutoConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_times_daytime_window() {
+        let schedule = times("07:00", "19:30");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T06:59:00Z")), "dark");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T07:00:00Z")), "light");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T19:29:00Z")), "light");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T19:30:00Z")), "dark");
+    }
+
+    #[test]
+    fn test_times_window_across_midnight() {
+        // Night-shift schedule: light from 22:00 until 06:00
+        let schedule = times("22:00", "06:00");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T23:00:00Z")), "light");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T03:00:00Z")), "light");
+        assert_eq!(schedule.polarity_at(&utc("2026-05-01T12:00:00Z")), "dark");
+    }
+
+    #[test]
+    fn test_times_use_local_clock() {
+        let schedule = times("07:00", "19:00");
+        let plus_two = FixedOffset::east_opt(2 * 3600).unwrap();
+        // 06:00 UTC is 08:00 at UTC+2
+        let now = utc("2026-05-01T06:00:00Z").with_timezone(&plus_two);
+        assert_eq!(schedule.polarity_at(&now), "light");
+    }
+
+    #[test]
+    fn test_solar_day_and_night() {
+        let amsterdam = solar(52.37, 4.90);
+        assert_eq!(amsterdam.polarity_at(&utc("2026-06-21T12:00:00Z")), "light");
+        assert_eq!(amsterdam.polarity_at(&utc("2026-06-21T23:00:00Z")), "dark");
+        // Sunrise on 2026-01-15 is around 07:45 UTC
+        assert_eq!(amsterdam.polarity_at(&utc("2026-01-15T07:30:00Z")), "dark");
+        assert_eq!(amsterdam.polarity_at(&utc("2026-01-15T08:00:00Z")), "light");
+    }
+
+    #[test]
+    fn test_solar_polar_day_and_night() {
+        let tromso = solar(69.65, 18.96);
+        // Midnight sun
+        assert_eq!(tromso.polarity_at(&utc("2026-06-21T23:00:00Z")), "light");
+        // Polar night, even at solar noon
+        assert_eq!(tromso.polarity_at(&utc("2026-12-21T10:45:00Z")), "dark");
+    }
+
+    #[test]
+    fn test_sun_elevation_equinox_noon() {
+        // Sun is nearly overhead at the equator at solar noon on the equinox
+        let elevation = sun_elevation(&utc("2026-03-20T12:07:00Z"), 0.0, 0.0);
+        assert!(elevation > 89.0, "elevation was {}", elevation);
+    }
+
+    #[test]
+    fn test_from_config_prefers_coordinates() {
+        let schedule = Schedule::from_config(&AutoConfig {
+            light: Some("07:00".to_string()),
+            dark: Some("19:00".to_string()),
+            latitude: Some(10.0),
+            longitude: Some(20.0),
+            interval: None,
+        })
+        .unwrap();
+        assert!(matches!(schedule, Schedule::Solar { .. }));
+    }
+
+    #[test]
+    fn test_from_config_errors() {
+        let error = |auto: AutoConfig| Schedule::from_config(&auto).unwrap_err();
+
+        assert!(error(AutoConfig::default()).contains("either"));
+        assert!(
+            error(AutoConfig {
+                latitude: Some(10.0),
+                ..AutoConfig::default()
+            })
+            .contains("together")
+        );
+        assert!(
+            error(AutoConfig {
+                latitude: Some(91.0),
+                longitude: Some(0.0),
+                ..AutoConfig::default()
+            })
+            .contains("latitude 91")
+        );
+        assert!(
+            error(AutoConfig {
+                light: Some("7am".to_string()),
+                dark: Some("19:00".to_string()),
+                ..AutoConfig::default()
+            })
+            .contains("light = \"7am\"")
+        );
+        assert!(
+            error(AutoConfig {
+                light: Some("07:00".to_string()),
+                dark: Some("07:00".to_string()),
+                ..AutoConfig::default()
+            })
+            .contains("differ")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            times("07:00", "19:30").to_string(),
+            "light at 07:00, dark at 19:30"
+        );
+        assert_eq!(
+            solar(52.372, 4.9).to_string(),
+            "sunrise/sunset at 52.37, 4.90"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// Theme state data persisted to disk
@@ -16,6 +18,43 @@ pub struct State {
     pub current_variant: String,
     /// Timestamp of last theme application
     pub last_applied: Option<String>,
+    /// How the state was last applied, None for states saved by older versions
+    pub provenance: Option<Provenance>,
+}
+
+/// Where an applied state came from and what it was rendered with
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Provenance {
+    pub applied_by: AppliedBy,
+    /// Templates hash the configs were rendered with, if templates are configured
+    pub templates_hash: Option<String>,
+    /// Directory the configs were rendered to, if templates are configured
+    pub cache_path: Option<PathBuf>,
+}
+
+/// What applied a state
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AppliedBy {
+    /// A `vogix` command: the theme flags, `pick`, `undo` or `redo`
+    Cli,
+    /// A request to `vogix daemon`
+    Daemon,
+    /// The `vogix auto` schedule
+    Auto,
+    /// `vogix refresh`
+    Refresh,
+}
+
+impl fmt::Display for AppliedBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cli => "cli",
+            Self::Daemon => "daemon",
+            Self::Auto => "auto",
+            Self::Refresh => "refresh",
+        })
+    }
 }
 
 impl Default for State {
@@ -25,10 +64,31 @@ impl Default for State {
             current_theme: "aikido".to_string(),
             current_variant: "night".to_string(),
             last_applied: None,
+            provenance: None,
         }
     }
 }
 
+/// Advisory `flock` on `{state_dir}/lock`, released when dropped
+#[derive(Debug)]
+pub struct StateLock {
+    _file: File,
+}
+
+impl StateLock {
+    /// Lock `dir`, creating it and the lockfile if needed
+    fn acquire(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join("lock"))?;
+        file.lock()?;
+        Ok(StateLock { _file: file })
+    }
+}
+
 impl State {
     /// Load state from the default state file location
     pub fn load() -> Result<Self> {
@@ -64,8 +124,39 @@ impl State {
 
         let contents = toml::to_string_pretty(&state_to_save).map_err(VogixError::TomlSerialize)?;
 
-        fs::write(state_path, contents)?;
-        Ok(())
+        write_atomic(state_path, contents.as_bytes())
+    }
+
+    /// The state file's contents as saved, or None if there is no state file
+    ///
+    /// Pass it to [`State::restore`] to undo a later save exactly, including
+    /// its `last_applied` and provenance.
+    pub fn snapshot() -> Result<Option<Vec<u8>>> {
+        match fs::read(Self::default_state_path()?) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Put back a state file taken with [`State::snapshot`]
+    pub fn restore(snapshot: Option<&[u8]>) -> Result<()> {
+        let state_path = Self::default_state_path()?;
+        match snapshot {
+            Some(contents) => write_atomic(&state_path, contents),
+            None => match fs::remove_file(&state_path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    /// Take the exclusive state lock, waiting for other vogix processes to release it
+    ///
+    /// Hold the returned guard across a read-modify-write of the state.
+    pub fn lock() -> Result<StateLock> {
+        StateLock::acquire(&Self::state_dir()?)
     }
 
     /// Get the default state file path
@@ -89,9 +180,31 @@ impl State {
     }
 }
 
+/// Replace the file at `path` with `contents`
+///
+/// Writes a temporary file and renames it into place, so readers and a crash
+/// mid-write only ever see the old or the new contents.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let written = File::create(&temp_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::thread;
     use tempfile::TempDir;
 
     #[test]
@@ -101,6 +214,7 @@ mod tests {
             current_theme: "test".to_string(),
             current_variant: "dark".to_string(),
             last_applied: None,
+            provenance: None,
         };
         assert_eq!(state.current_scheme, Scheme::Vogix16);
         assert_eq!(state.current_theme, "test");
@@ -126,6 +240,7 @@ mod tests {
             current_theme: "rose-pine".to_string(),
             current_variant: "moon".to_string(),
             last_applied: None,
+            provenance: None,
         };
 
         // Save state to temp path
@@ -140,6 +255,93 @@ mod tests {
         assert!(loaded.last_applied.is_some()); // save_to() sets timestamp
     }
 
+    #[test]
+    fn test_state_provenance_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("state.toml");
+        let provenance = Provenance {
+            applied_by: AppliedBy::Auto,
+            templates_hash: Some("abc123".to_string()),
+            cache_path: Some(PathBuf::from("/cache/abc123/base16/gruvbox/dark")),
+        };
+        let state = State {
+            provenance: Some(provenance.clone()),
+            ..State::default()
+        };
+
+        state.save_to(&state_path).unwrap();
+        let contents = fs::read_to_string(&state_path).unwrap();
+        assert!(contents.contains("[provenance]\napplied_by = \"auto\""));
+
+        let loaded = State::load_from(&state_path).unwrap();
+        assert_eq!(loaded.provenance, Some(provenance));
+    }
+
+    #[test]
+    fn test_state_without_provenance_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("state.toml");
+        fs::write(
+            &state_path,
+            "current_theme = \"gruvbox\"\ncurrent_variant = \"dark\"\n",
+        )
+        .unwrap();
+
+        let loaded = State::load_from(&state_path).unwrap();
+        assert_eq!(loaded.provenance, None);
+    }
+
+    #[test]
+    fn test_state_save_replaces_atomically() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("state.toml");
+        State::default().save_to(&state_path).unwrap();
+        State::default().save_to(&state_path).unwrap();
+
+        // No temporary files are left behind
+        let entries: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec!["state.toml"]);
+    }
+
+    #[test]
+    fn test_concurrent_locked_switches() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("state.toml");
+        State {
+            current_variant: String::new(),
+            ..State::default()
+        }
+        .save_to(&state_path)
+        .unwrap();
+
+        // Each switch appends to the variant, so a lost update or a torn
+        // write shows up as a short or unparsable state
+        let switches: Vec<_> = (0..32)
+            .map(|i| {
+                let dir = temp_dir.path().to_path_buf();
+                let state_path = state_path.clone();
+                thread::spawn(move || {
+                    let _lock = StateLock::acquire(&dir).unwrap();
+                    let mut state = State::load_from(&state_path).unwrap();
+                    state.current_theme = format!("theme-{}", i);
+                    state.current_variant.push('x');
+                    thread::yield_now();
+                    state.save_to(&state_path).unwrap();
+                })
+            })
+            .collect();
+        for switch in switches {
+            switch.join().unwrap();
+        }
+
+        let state = State::load_from(&state_path).unwrap();
+        assert_eq!(state.current_variant, "x".repeat(32));
+        assert!(state.current_theme.starts_with("theme-"));
+    }
+
     #[test]
     fn test_state_load_missing_returns_default() {
         let temp_dir = TempDir::new().unwrap();
@@ -167,6 +369,7 @@ mod tests {
             current_theme: "dracula".to_string(),
             current_variant: "default".to_string(),
             last_applied: Some("2024-01-01T00:00:00Z".to_string()),
+            provenance: None,
         };
 
         let serialized = toml::to_string_pretty(&state).unwrap();
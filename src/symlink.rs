@@ -2,7 +2,7 @@ use crate::errors::{Result, VogixError};
 use crate::state::State;
 use log::debug;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Manages symlinks for theme switching
 pub struct SymlinkManager {
@@ -58,23 +58,8 @@ impl SymlinkManager {
         fs::create_dir_all(&state_dir)?;
         let current_link = state_dir.join("current-theme");
 
-        // Remove existing symlink if present
-        if current_link.exists() || current_link.is_symlink() {
-            if current_link.is_symlink() {
-                fs::remove_file(&current_link)?;
-            } else {
-                return Err(VogixError::symlink(format!(
-                    "'current-theme' path exists but is not a symlink: {}",
-                    current_link.display()
-                )));
-            }
-        }
-
-        // Create new symlink (absolute path to theme package)
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(&target_path, &current_link).map_err(|e| {
-            VogixError::symlink_with_source("failed to create 'current-theme' symlink", e)
-        })?;
+        // Absolute path to theme package
+        replace_symlink(&target_path, &current_link)?;
 
         debug!(
             "Updated current-theme: {} -> {}",
@@ -87,7 +72,7 @@ impl SymlinkManager {
 
     /// Update the 'current-theme' symlink in state directory to point to cached configs
     /// Path: ~/.local/state/vogix/current-theme -> ~/.cache/vogix/themes/{hash}/...
-    pub fn update_state_current_symlink(&self, cache_path: &std::path::Path) -> Result<()> {
+    pub fn update_state_current_symlink(&self, cache_path: &Path) -> Result<()> {
         let state_dir = State::state_dir()?;
 
         // Create state directory if it doesn't exist
@@ -95,23 +80,8 @@ impl SymlinkManager {
 
         let current_link = state_dir.join("current-theme");
 
-        // Remove existing symlink if present
-        if current_link.exists() || current_link.is_symlink() {
-            if current_link.is_symlink() {
-                fs::remove_file(&current_link)?;
-            } else {
-                return Err(VogixError::symlink(format!(
-                    "'current-theme' path exists but is not a symlink: {}",
-                    current_link.display()
-                )));
-            }
-        }
-
-        // Create new symlink (absolute path to cache)
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(cache_path, &current_link).map_err(|e| {
-            VogixError::symlink_with_source("failed to create state 'current-theme' symlink", e)
-        })?;
+        // Absolute path to cache
+        replace_symlink(cache_path, &current_link)?;
 
         debug!(
             "Updated state current-theme: {} -> {}",
@@ -121,6 +91,70 @@ impl SymlinkManager {
 
         Ok(())
     }
+
+    /// Where the 'current-theme' symlink points, or None if there is none yet
+    pub fn current_target(&self) -> Result<Option<PathBuf>> {
+        let current_link = State::state_dir()?.join("current-theme");
+        if !current_link.is_symlink() {
+            return Ok(None);
+        }
+        fs::read_link(&current_link).map(Some).map_err(|e| {
+            VogixError::symlink_with_source("failed to read 'current-theme' symlink", e)
+        })
+    }
+
+    /// Point the 'current-theme' symlink back at a target from [`Self::current_target`]
+    ///
+    /// Removes the symlink if there was none.
+    pub fn restore_current_symlink(&self, target: Option<&Path>) -> Result<()> {
+        let current_link = State::state_dir()?.join("current-theme");
+        match target {
+            Some(target) => replace_symlink(target, &current_link)?,
+            None if current_link.is_symlink() => fs::remove_file(&current_link)?,
+            None => {}
+        }
+        debug!("Restored current-theme to {:?}", target);
+        Ok(())
+    }
+}
+
+/// Point `link` at `target`, replacing any existing symlink atomically
+///
+/// The new link is created under a temporary name and renamed over `link`,
+/// so readers see either the old or the new target, never a missing path.
+fn replace_symlink(target: &Path, link: &Path) -> Result<()> {
+    if link.exists() && !link.is_symlink() {
+        return Err(VogixError::symlink(format!(
+            "'{}' path exists but is not a symlink: {}",
+            link_name(link),
+            link.display()
+        )));
+    }
+
+    let temp_link = link.with_file_name(format!(".{}.{}.tmp", link_name(link), std::process::id()));
+    // Left behind if an earlier run with the same pid was killed mid-swap
+    if temp_link.is_symlink() {
+        fs::remove_file(&temp_link)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(target, &temp_link).map_err(|e| {
+        VogixError::symlink_with_source(
+            format!("failed to create '{}' symlink", link_name(link)),
+            e,
+        )
+    })?;
+
+    fs::rename(&temp_link, link).map_err(|e| {
+        let _ = fs::remove_file(&temp_link);
+        VogixError::symlink_with_source(format!("failed to replace '{}'", link_name(link)), e)
+    })
+}
+
+fn link_name(link: &Path) -> String {
+    link.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
 }
 
 impl Default for SymlinkManager {
@@ -132,6 +166,9 @@ impl Default for SymlinkManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
     use tempfile::TempDir;
 
     #[test]
@@ -174,60 +211,92 @@ mod tests {
     }
 
     #[test]
-    fn test_update_state_current_symlink() {
+    fn test_replace_symlink_creates_link() {
         let temp_dir = TempDir::new().unwrap();
-        let state_dir = temp_dir.path().join("state/vogix");
         let cache_path = temp_dir
             .path()
             .join("cache/themes/hash123/vogix16/aikido/dark");
-
-        // Create cache directory and state directory
         fs::create_dir_all(&cache_path).unwrap();
-        fs::create_dir_all(&state_dir).unwrap();
-
-        // Use a manager with custom state dir via direct symlink creation
-        // (update_state_current_symlink uses State::state_dir() which we can't override)
-        let current_link = state_dir.join("current-theme");
+        let current_link = temp_dir.path().join("current-theme");
 
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(&cache_path, &current_link).unwrap();
+        replace_symlink(&cache_path, &current_link).unwrap();
 
-        // Verify symlink was created
         assert!(current_link.is_symlink());
-
-        // Verify symlink points to cache path
-        let target = fs::read_link(&current_link).unwrap();
-        assert_eq!(target, cache_path);
+        assert_eq!(fs::read_link(&current_link).unwrap(), cache_path);
     }
 
     #[test]
-    fn test_update_state_current_symlink_replaces_existing() {
+    fn test_replace_symlink_replaces_existing() {
         let temp_dir = TempDir::new().unwrap();
-        let state_dir = temp_dir.path().join("state/vogix");
         let cache_path1 = temp_dir
             .path()
             .join("cache/themes/hash1/vogix16/aikido/dark");
         let cache_path2 = temp_dir
             .path()
             .join("cache/themes/hash2/vogix16/aikido/light");
-
         fs::create_dir_all(&cache_path1).unwrap();
         fs::create_dir_all(&cache_path2).unwrap();
-        fs::create_dir_all(&state_dir).unwrap();
+        let current_link = temp_dir.path().join("current-theme");
+
+        replace_symlink(&cache_path1, &current_link).unwrap();
+        replace_symlink(&cache_path2, &current_link).unwrap();
+
+        assert_eq!(fs::read_link(&current_link).unwrap(), cache_path2);
+        // Only the link itself is left, no temporary names
+        let entries: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.contains(&"current-theme".into()));
+    }
 
-        let current_link = state_dir.join("current-theme");
+    #[test]
+    fn test_replace_symlink_refuses_non_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let current_link = temp_dir.path().join("current-theme");
+        fs::create_dir_all(&current_link).unwrap();
+
+        let err = replace_symlink(temp_dir.path(), &current_link).unwrap_err();
+
+        assert!(err.to_string().contains("is not a symlink"));
+        assert!(current_link.is_dir() && !current_link.is_symlink());
+    }
+
+    #[test]
+    fn test_replace_symlink_never_missing_for_readers() {
+        let temp_dir = TempDir::new().unwrap();
+        let dark = temp_dir.path().join("aikido-dark");
+        let light = temp_dir.path().join("aikido-light");
+        fs::create_dir_all(&dark).unwrap();
+        fs::create_dir_all(&light).unwrap();
+        let current_link = temp_dir.path().join("current-theme");
+        replace_symlink(&dark, &current_link).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let done = Arc::clone(&done);
+            let current_link = current_link.clone();
+            thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::Relaxed) {
+                    // Follows the link, so a missing link or target is ENOENT
+                    if let Err(e) = fs::metadata(&current_link) {
+                        panic!("reader saw {:?} after {} reads", e.kind(), reads);
+                    }
+                    reads += 1;
+                }
+                reads
+            })
+        };
+
+        for i in 0..2000 {
+            let target = if i % 2 == 0 { &light } else { &dark };
+            replace_symlink(target, &current_link).unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
 
-        // Create first symlink
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(&cache_path1, &current_link).unwrap();
-        let target1 = fs::read_link(&current_link).unwrap();
-        assert_eq!(target1, cache_path1);
-
-        // Replace with second symlink
-        fs::remove_file(&current_link).unwrap();
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(&cache_path2, &current_link).unwrap();
-        let target2 = fs::read_link(&current_link).unwrap();
-        assert_eq!(target2, cache_path2);
+        let reads = reader.join().unwrap();
+        assert!(reads > 0);
     }
 }
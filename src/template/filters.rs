@@ -2,12 +2,14 @@
 //!
 //! These filters transform color values during template rendering.
 
+use crate::color::{self, Color};
 use std::collections::HashMap;
 use tera::Value;
 
 /// Convert hex color to RGB format for applications like ripgrep
 ///
-/// Input: "#RRGGBB" -> Output: "0xRR,0xGG,0xBB"
+/// Input: "#RRGGBB" -> Output: "0xRR,0xGG,0xBB". The alpha of "#RRGGBBAA" is
+/// left out, as ripgrep has no transparency.
 ///
 /// # Example
 /// ```ignore
@@ -19,9 +21,9 @@ pub fn hex_to_rgb(value: &Value, _args: &HashMap<String, Value>) -> tera::Result
         .ok_or_else(|| tera::Error::msg("hex_to_rgb expects a string"))?;
 
     let clean = hex.trim_start_matches('#');
-    if clean.len() != 6 {
+    if clean.len() != 6 && clean.len() != 8 {
         return Err(tera::Error::msg(format!(
-            "hex_to_rgb expects 6 hex digits, got: {}",
+            "hex_to_rgb expects 6 or 8 hex digits, got: {}",
             hex
         )));
     }
@@ -49,6 +51,265 @@ pub fn strip_hash(value: &Value, _args: &HashMap<String, Value>) -> tera::Result
     Ok(Value::String(hex.trim_start_matches('#').to_string()))
 }
 
+/// Convert a hex color to CSS decimal RGB
+///
+/// Input: "#RRGGBB" -> Output: "rgb(R,G,B)"
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | rgb }}  // "#FF5733" -> "rgb(255,87,51)"
+/// ```
+pub fn rgb(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let (r, g, b) = parse_color(value, "rgb")?;
+    Ok(Value::String(format!("rgb({},{},{})", r, g, b)))
+}
+
+/// Convert a hex color to CSS decimal RGB with alpha
+///
+/// Input: "#RRGGBB" -> Output: "rgba(R,G,B,A)", with `alpha` from 0.0 to 1.0
+/// (default: the color's own alpha, 1.0 for "#RRGGBB")
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | rgba(alpha=0.5) }}  // "#FF5733" -> "rgba(255,87,51,0.5)"
+/// ```
+pub fn rgba(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let color = parse(value, "rgba")?;
+    let (r, g, b) = color.to_rgb();
+    let alpha = alpha_arg(args, "rgba")?
+        .unwrap_or_else(|| (f64::from(color.a) / 255.0 * 1000.0).round() / 1000.0);
+    Ok(Value::String(format!("rgba({},{},{},{})", r, g, b, alpha)))
+}
+
+/// Convert a hex color to channels from 0.0 to 1.0
+///
+/// Input: "#RRGGBB" -> Output: "R,G,B" with three decimals
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | rgb_float }}  // "#FF5733" -> "1.000,0.341,0.200"
+/// ```
+pub fn rgb_float(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let (r, g, b) = parse_color(value, "rgb_float")?;
+    let [r, g, b] = [r, g, b].map(|c| f64::from(c) / 255.0);
+    Ok(Value::String(format!("{:.3},{:.3},{:.3}", r, g, b)))
+}
+
+/// Convert a hex color to CSS HSL
+///
+/// Input: "#RRGGBB" -> Output: "hsl(H,S%,L%)", rounded to whole numbers
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | hsl }}  // "#FF5733" -> "hsl(11,100%,60%)"
+/// ```
+pub fn hsl(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let (h, s, l) = color::to_hsl(parse_color(value, "hsl")?);
+    Ok(Value::String(format!(
+        "hsl({},{}%,{}%)",
+        h.round(),
+        s.round(),
+        l.round()
+    )))
+}
+
+/// Convert a hex color to HSV
+///
+/// Input: "#RRGGBB" -> Output: "hsv(H,S%,V%)", rounded to whole numbers
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | hsv }}  // "#FF5733" -> "hsv(11,80%,100%)"
+/// ```
+pub fn hsv(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let (h, s, v) = color::to_hsv(parse_color(value, "hsv")?);
+    Ok(Value::String(format!(
+        "hsv({},{}%,{}%)",
+        h.round(),
+        s.round(),
+        v.round()
+    )))
+}
+
+/// Append an alpha channel to a hex color
+///
+/// Input: "#RRGGBB" -> Output: "#RRGGBBAA", with `alpha` from 0.0 to 1.0
+/// (default: the color's own alpha, 1.0 for "#RRGGBB")
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | hex8(alpha=0.5) }}  // "#FF5733" -> "#ff573380"
+/// ```
+pub fn hex8(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let color = parse(value, "hex8")?;
+    let (r, g, b) = color.to_rgb();
+    let alpha = alpha_byte(args, "hex8", color)?;
+    Ok(Value::String(format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        r, g, b, alpha
+    )))
+}
+
+/// Convert a hex color to alpha-first hex, as used by Qt and some X resources
+///
+/// Input: "#RRGGBB" -> Output: "0xAARRGGBB", with `alpha` from 0.0 to 1.0
+/// (default: the color's own alpha, 1.0 for "#RRGGBB")
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | argb(alpha=0.5) }}  // "#FF5733" -> "0x80ff5733"
+/// ```
+pub fn argb(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let color = parse(value, "argb")?;
+    let (r, g, b) = color.to_rgb();
+    let alpha = alpha_byte(args, "argb", color)?;
+    Ok(Value::String(format!(
+        "0x{:02x}{:02x}{:02x}{:02x}",
+        alpha, r, g, b
+    )))
+}
+
+/// Nearest xterm 256-color palette index
+///
+/// Input: "#RRGGBB" -> Output: a number from 16 to 255
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | xterm256 }}  // "#FF0000" -> 196
+/// ```
+pub fn xterm256(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let index = color::nearest_ansi256(parse_color(value, "xterm256")?);
+    Ok(Value::from(index))
+}
+
+/// Name of the nearest ANSI color, in the form tmux accepts
+///
+/// Input: "#RRGGBB" -> Output: "red", "brightblue", ...
+///
+/// # Example
+/// ```ignore
+/// {{ colors.red | ansi16 }}  // "#FF0000" -> "brightred"
+/// ```
+pub fn ansi16(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let name = color::nearest_ansi16(parse_color(value, "ansi16")?);
+    Ok(Value::String(name.to_string()))
+}
+
+/// Raise OKLCH lightness by `amount` from 0.0 to 1.0
+///
+/// # Example
+/// ```ignore
+/// {{ colors.base02 | lighten(amount=0.1) }}  // "#504945" -> "#6c6560"
+/// ```
+pub fn lighten(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let input = parse(value, "lighten")?;
+    let rgb = input.to_rgb();
+    let amount = amount_arg(args, "lighten")?;
+    Ok(with_alpha(color::lighten(rgb, amount), input.a))
+}
+
+/// Lower OKLCH lightness by `amount` from 0.0 to 1.0
+///
+/// # Example
+/// ```ignore
+/// {{ colors.base02 | darken(amount=0.1) }}  // "#504945" -> "#362f2b"
+/// ```
+pub fn darken(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let input = parse(value, "darken")?;
+    let rgb = input.to_rgb();
+    let amount = amount_arg(args, "darken")?;
+    Ok(with_alpha(color::lighten(rgb, -amount), input.a))
+}
+
+/// Scale OKLCH chroma up by `amount`, e.g. 0.2 for 20% more colorful
+///
+/// # Example
+/// ```ignore
+/// {{ colors.base0B | saturate(amount=0.2) }}
+/// ```
+pub fn saturate(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let input = parse(value, "saturate")?;
+    let rgb = input.to_rgb();
+    let amount = amount_arg(args, "saturate")?;
+    Ok(with_alpha(color::saturate(rgb, amount), input.a))
+}
+
+/// Scale OKLCH chroma down by `amount` from 0.0 to 1.0, where 1.0 gives gray
+///
+/// # Example
+/// ```ignore
+/// {{ colors.base0B | desaturate(amount=0.5) }}
+/// ```
+pub fn desaturate(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let input = parse(value, "desaturate")?;
+    let rgb = input.to_rgb();
+    let amount = amount_arg(args, "desaturate")?;
+    Ok(with_alpha(color::saturate(rgb, -amount), input.a))
+}
+
+/// Parse a filter input in any format `Color::parse` accepts
+pub(super) fn parse(value: &Value, filter: &str) -> tera::Result<Color> {
+    let hex = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg(format!("{} expects a string", filter)))?;
+    Color::parse(hex)
+        .ok_or_else(|| tera::Error::msg(format!("{} expects a color, got: {}", filter, hex)))
+}
+
+/// Parse a filter input to its RGB components, for outputs without alpha
+pub(super) fn parse_color(value: &Value, filter: &str) -> tera::Result<(u8, u8, u8)> {
+    parse(value, filter).map(Color::to_rgb)
+}
+
+/// A derived color as hex, keeping the input's alpha
+pub(super) fn with_alpha((r, g, b): (u8, u8, u8), a: u8) -> Value {
+    Value::String(
+        Color {
+            a,
+            ..Color::rgb(r, g, b)
+        }
+        .to_string(),
+    )
+}
+
+/// The optional `alpha` argument, from 0.0 (transparent) to 1.0 (opaque)
+fn alpha_arg(args: &HashMap<String, Value>, filter: &str) -> tera::Result<Option<f64>> {
+    let Some(alpha) = args.get("alpha") else {
+        return Ok(None);
+    };
+    alpha
+        .as_f64()
+        .filter(|alpha| (0.0..=1.0).contains(alpha))
+        .map(Some)
+        .ok_or_else(|| {
+            tera::Error::msg(format!(
+                "{} expects alpha between 0.0 and 1.0, got: {}",
+                filter, alpha
+            ))
+        })
+}
+
+/// The required non-negative `amount` argument of the color math filters
+fn amount_arg(args: &HashMap<String, Value>, filter: &str) -> tera::Result<f64> {
+    let amount = args
+        .get("amount")
+        .ok_or_else(|| tera::Error::msg(format!("{} expects an amount argument", filter)))?;
+    amount
+        .as_f64()
+        .filter(|amount| *amount >= 0.0)
+        .ok_or_else(|| {
+            tera::Error::msg(format!(
+                "{} expects a non-negative amount, got: {}",
+                filter, amount
+            ))
+        })
+}
+
+/// The `alpha` argument as a byte, or the color's own alpha without one
+fn alpha_byte(args: &HashMap<String, Value>, filter: &str, color: Color) -> tera::Result<u8> {
+    Ok(alpha_arg(args, filter)?.map_or(color.a, |alpha| (alpha * 255.0).round() as u8))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,6 +328,13 @@ mod tests {
         assert_eq!(result, Value::String("0xab,0xcd,0xef".to_string()));
     }
 
+    #[test]
+    fn test_hex_to_rgb_drops_alpha() {
+        let value = Value::String("#abcdef80".to_string());
+        let result = hex_to_rgb(&value, &HashMap::new()).unwrap();
+        assert_eq!(result, Value::String("0xab,0xcd,0xef".to_string()));
+    }
+
     #[test]
     fn test_hex_to_rgb_invalid_length() {
         let value = Value::String("#FFF".to_string());
@@ -87,4 +355,153 @@ mod tests {
         let result = strip_hash(&value, &HashMap::new()).unwrap();
         assert_eq!(result, Value::String("1e90ff".to_string()));
     }
+
+    type FilterFn = fn(&Value, &HashMap<String, Value>) -> tera::Result<Value>;
+
+    fn args(alpha: f64) -> HashMap<String, Value> {
+        HashMap::from([("alpha".to_string(), Value::from(alpha))])
+    }
+
+    fn apply(filter: FilterFn, hex: &str, args: &HashMap<String, Value>) -> Value {
+        filter(&Value::String(hex.to_string()), args).unwrap()
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(apply(rgb, "#FF5733", &HashMap::new()), "rgb(255,87,51)");
+        assert_eq!(apply(rgb, "#000000", &HashMap::new()), "rgb(0,0,0)");
+    }
+
+    #[test]
+    fn test_rgba() {
+        assert_eq!(apply(rgba, "#ff5733", &args(0.5)), "rgba(255,87,51,0.5)");
+        assert_eq!(apply(rgba, "#ff5733", &HashMap::new()), "rgba(255,87,51,1)");
+    }
+
+    #[test]
+    fn test_rgb_float() {
+        assert_eq!(
+            apply(rgb_float, "#FF5733", &HashMap::new()),
+            "1.000,0.341,0.200"
+        );
+    }
+
+    #[test]
+    fn test_hsl() {
+        assert_eq!(apply(hsl, "#FF5733", &HashMap::new()), "hsl(11,100%,60%)");
+        assert_eq!(apply(hsl, "#808080", &HashMap::new()), "hsl(0,0%,50%)");
+    }
+
+    #[test]
+    fn test_hsv() {
+        assert_eq!(apply(hsv, "#FF5733", &HashMap::new()), "hsv(11,80%,100%)");
+        assert_eq!(apply(hsv, "#000000", &HashMap::new()), "hsv(0,0%,0%)");
+    }
+
+    #[test]
+    fn test_hex8() {
+        assert_eq!(apply(hex8, "#FF5733", &args(0.5)), "#ff573380");
+        assert_eq!(apply(hex8, "#FF5733", &HashMap::new()), "#ff5733ff");
+        assert_eq!(apply(hex8, "#FF5733", &args(0.0)), "#ff573300");
+    }
+
+    #[test]
+    fn test_alpha_defaults_to_the_colors_own() {
+        assert_eq!(apply(hex8, "#ff573380", &HashMap::new()), "#ff573380");
+        assert_eq!(apply(argb, "#ff573380", &HashMap::new()), "0x80ff5733");
+        assert_eq!(
+            apply(rgba, "#ff573380", &HashMap::new()),
+            "rgba(255,87,51,0.502)"
+        );
+        // An explicit alpha replaces it
+        assert_eq!(apply(hex8, "#ff573380", &args(1.0)), "#ff5733ff");
+    }
+
+    #[test]
+    fn test_argb() {
+        assert_eq!(apply(argb, "#FF5733", &args(0.5)), "0x80ff5733");
+        assert_eq!(apply(argb, "#FF5733", &HashMap::new()), "0xffff5733");
+    }
+
+    #[test]
+    fn test_alpha_out_of_range() {
+        let value = Value::String("#FF5733".to_string());
+        let err = rgba(&value, &args(1.5)).unwrap_err();
+        assert!(err.to_string().contains("alpha between 0.0 and 1.0"));
+
+        let text = HashMap::from([("alpha".to_string(), Value::from("half"))]);
+        assert!(hex8(&value, &text).is_err());
+    }
+
+    #[test]
+    fn test_xterm256() {
+        assert_eq!(apply(xterm256, "#FF0000", &HashMap::new()), 196);
+        assert_eq!(apply(xterm256, "#080808", &HashMap::new()), 232);
+    }
+
+    #[test]
+    fn test_ansi16() {
+        assert_eq!(apply(ansi16, "#FF0000", &HashMap::new()), "brightred");
+        assert_eq!(apply(ansi16, "#b8bb26", &HashMap::new()), "yellow");
+    }
+
+    #[test]
+    fn test_conversions_reject_invalid_colors() {
+        let filters: [FilterFn; 8] = [rgb, rgba, rgb_float, hsl, hsv, hex8, argb, ansi16];
+        for filter in filters {
+            assert!(filter(&Value::String("#FFFF".to_string()), &HashMap::new()).is_err());
+            assert!(filter(&Value::from(42), &HashMap::new()).is_err());
+        }
+    }
+
+    fn amount(amount: f64) -> HashMap<String, Value> {
+        HashMap::from([("amount".to_string(), Value::from(amount))])
+    }
+
+    #[test]
+    fn test_lighten_and_darken() {
+        assert_eq!(apply(lighten, "#504945", &amount(0.1)), "#6c6560");
+        assert_eq!(apply(darken, "#504945", &amount(0.1)), "#362f2b");
+        assert_eq!(apply(lighten, "#504945", &amount(0.0)), "#504945");
+        assert_eq!(apply(lighten, "#504945", &amount(1.0)), "#ffffff");
+    }
+
+    #[test]
+    fn test_color_math_keeps_alpha() {
+        assert_eq!(apply(lighten, "#50494580", &amount(0.1)), "#6c656080");
+        assert_eq!(
+            apply(desaturate, "#ff000080", &amount(1.0))
+                .as_str()
+                .unwrap()
+                .len(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_saturate_and_desaturate() {
+        assert_eq!(apply(desaturate, "#808080", &amount(0.5)), "#808080");
+        let gray = apply(desaturate, "#fb4934", &amount(1.0));
+        let (r, g, b) = color::Color::parse(gray.as_str().unwrap())
+            .unwrap()
+            .to_rgb();
+        assert!(r.abs_diff(g) <= 1 && g.abs_diff(b) <= 1);
+
+        let vivid = apply(saturate, "#83a598", &amount(0.5));
+        let (_, before, _) = color::to_oklch((0x83, 0xa5, 0x98));
+        let (_, after, _) = color::to_oklch(
+            color::Color::parse(vivid.as_str().unwrap())
+                .unwrap()
+                .to_rgb(),
+        );
+        assert!(after > before);
+    }
+
+    #[test]
+    fn test_color_math_amount_required() {
+        let value = Value::String("#504945".to_string());
+        let err = lighten(&value, &HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("expects an amount"));
+        assert!(darken(&value, &amount(-0.1)).is_err());
+    }
 }
@@ -0,0 +1,213 @@
+//! Custom Tera template functions for deriving colors
+//!
+//! Unlike filters, these combine several colors, so they take every color
+//! as a named argument. Colors are interpolated in OKLab, and their alpha
+//! linearly.
+
+use super::filters::{parse, parse_color, with_alpha};
+use crate::color::{self, Color};
+use std::collections::HashMap;
+use tera::Value;
+
+/// Blend two colors, from `a` at `t=0.0` to `b` at `t=1.0` (default 0.5)
+///
+/// # Example
+/// ```ignore
+/// {{ mix(a=colors.base00, b=colors.base08, t=0.2) }}
+/// ```
+pub fn mix(args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let a = color_arg(args, "mix", "a")?;
+    let b = color_arg(args, "mix", "b")?;
+    let t = match args.get("t") {
+        Some(t) => t
+            .as_f64()
+            .filter(|t| (0.0..=1.0).contains(t))
+            .ok_or_else(|| {
+                tera::Error::msg(format!("mix expects t between 0.0 and 1.0, got: {}", t))
+            })?,
+        None => 0.5,
+    };
+    Ok(with_alpha(
+        color::mix(a.to_rgb(), b.to_rgb(), t),
+        mix_alpha(a, b, t),
+    ))
+}
+
+/// `n` evenly spaced colors from `a` to `b`, both included
+///
+/// # Example
+/// ```ignore
+/// {% for shade in ramp(a=colors.base0B, b=colors.base08, n=4) %}
+/// gradient_{{ loop.index }} = "{{ shade }}"
+/// {% endfor %}
+/// ```
+pub fn ramp(args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let a = color_arg(args, "ramp", "a")?;
+    let b = color_arg(args, "ramp", "b")?;
+    let n = args
+        .get("n")
+        .ok_or_else(|| tera::Error::msg("ramp expects an n argument"))?;
+    let n = n
+        .as_u64()
+        .filter(|n| *n >= 2)
+        .ok_or_else(|| tera::Error::msg(format!("ramp expects n of at least 2, got: {}", n)))?;
+
+    let steps = (n - 1) as f64;
+    Ok(Value::Array(
+        color::ramp(a.to_rgb(), b.to_rgb(), n as usize)
+            .into_iter()
+            .enumerate()
+            .map(|(i, rgb)| with_alpha(rgb, mix_alpha(a, b, i as f64 / steps)))
+            .collect(),
+    ))
+}
+
+/// The candidate with the most contrast against `bg`, as given
+///
+/// Contrast is the difference in OKLab lightness.
+///
+/// # Example
+/// ```ignore
+/// {{ readable_on(bg=colors.base0D, candidates=[colors.base00, colors.base07]) }}
+/// ```
+pub fn readable_on(args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let background = color_arg(args, "readable_on", "bg")?.to_rgb();
+    let candidates = args
+        .get("candidates")
+        .and_then(Value::as_array)
+        .filter(|candidates| !candidates.is_empty())
+        .ok_or_else(|| tera::Error::msg("readable_on expects a non-empty candidates array"))?;
+    let colors = candidates
+        .iter()
+        .map(|candidate| parse_color(candidate, "readable_on"))
+        .collect::<tera::Result<Vec<_>>>()?;
+
+    let best = color::most_readable(background, &colors).unwrap_or(0);
+    Ok(candidates[best].clone())
+}
+
+/// A required color argument
+fn color_arg(args: &HashMap<String, Value>, function: &str, name: &str) -> tera::Result<Color> {
+    let value = args
+        .get(name)
+        .ok_or_else(|| tera::Error::msg(format!("{} expects a {} argument", function, name)))?;
+    parse(value, function)
+}
+
+/// Interpolate alpha from `a` (t = 0.0) to `b` (t = 1.0)
+fn mix_alpha(a: Color, b: Color, t: f64) -> u8 {
+    (f64::from(a.a) + (f64::from(b.a) - f64::from(a.a)) * t).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_mix() {
+        let black_white = [("a", Value::from("#000000")), ("b", Value::from("#FFFFFF"))];
+        assert_eq!(mix(&args(&black_white)).unwrap(), "#636363");
+
+        let mut to_white = black_white.to_vec();
+        to_white.push(("t", Value::from(1)));
+        assert_eq!(mix(&args(&to_white)).unwrap(), "#ffffff");
+    }
+
+    #[test]
+    fn test_mix_interpolates_alpha() {
+        let result = mix(&args(&[
+            ("a", Value::from("#00000000")),
+            ("b", Value::from("#ffffff")),
+            ("t", Value::from(1)),
+        ]))
+        .unwrap();
+        assert_eq!(result, "#ffffff");
+
+        let result = ramp(&args(&[
+            ("a", Value::from("#00000000")),
+            ("b", Value::from("#000000")),
+            ("n", Value::from(3)),
+        ]))
+        .unwrap();
+        assert_eq!(
+            result,
+            Value::Array(vec![
+                "#00000000".into(),
+                "#00000080".into(),
+                "#000000".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mix_invalid_arguments() {
+        assert!(mix(&args(&[("a", Value::from("#000000"))])).is_err());
+
+        let err = mix(&args(&[
+            ("a", Value::from("#000000")),
+            ("b", Value::from("#ffffff")),
+            ("t", Value::from(1.5)),
+        ]))
+        .unwrap_err();
+        assert!(err.to_string().contains("t between 0.0 and 1.0"));
+    }
+
+    #[test]
+    fn test_ramp() {
+        let result = ramp(&args(&[
+            ("a", Value::from("#000000")),
+            ("b", Value::from("#ffffff")),
+            ("n", Value::from(3)),
+        ]))
+        .unwrap();
+        assert_eq!(
+            result,
+            Value::Array(vec!["#000000".into(), "#636363".into(), "#ffffff".into()])
+        );
+    }
+
+    #[test]
+    fn test_ramp_needs_two_colors() {
+        let result = ramp(&args(&[
+            ("a", Value::from("#000000")),
+            ("b", Value::from("#ffffff")),
+            ("n", Value::from(1)),
+        ]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_readable_on() {
+        let candidates = Value::from(vec!["#282828", "#FBF1C7"]);
+        let on_dark = args(&[
+            ("bg", Value::from("#3c3836")),
+            ("candidates", candidates.clone()),
+        ]);
+        assert_eq!(readable_on(&on_dark).unwrap(), "#FBF1C7");
+
+        let on_light = args(&[("bg", Value::from("#d5c4a1")), ("candidates", candidates)]);
+        assert_eq!(readable_on(&on_light).unwrap(), "#282828");
+    }
+
+    #[test]
+    fn test_readable_on_invalid_candidates() {
+        let empty = args(&[
+            ("bg", Value::from("#282828")),
+            ("candidates", Value::Array(Vec::new())),
+        ]);
+        assert!(readable_on(&empty).is_err());
+
+        let invalid = args(&[
+            ("bg", Value::from("#282828")),
+            ("candidates", Value::from(vec!["#ffff"])),
+        ]);
+        assert!(readable_on(&invalid).is_err());
+    }
+}
@@ -3,7 +3,8 @@
 //! Uses Tera template engine with custom filters for color manipulation.
 //!
 //! # Module Structure
-//! - `filters`: Custom Tera filters (hex_to_rgb, strip_hash)
+//! - `filters`: Custom Tera filters for color formats (hex_to_rgb, rgba, hsl, ...)
+//! - `functions`: Custom Tera functions combining colors (mix, ramp, readable_on)
 //! - `render`: Core rendering functions
 //!
 //! # Template Syntax
@@ -12,9 +13,13 @@
 //! background = "{{ colors.base00 }}"
 //! foreground = "{{ colors.base05 }}"
 //! rgb_color = "{{ colors.red | hex_to_rgb }}"
+//! selection = "{{ colors.base02 | rgba(alpha=0.8) }}"
+//! hover = "{{ colors.base02 | lighten(amount=0.05) }}"
+//! border = "{{ mix(a=colors.base00, b=colors.base0D, t=0.3) }}"
 //! ```
 
 pub mod filters;
+pub mod functions;
 mod render;
 #[cfg(test)]
 mod tests;
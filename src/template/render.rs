@@ -2,9 +2,9 @@
 //!
 //! Core rendering logic using Tera template engine.
 
-use super::filters;
+use super::{filters, functions};
 use crate::errors::{Result, VogixError};
-use std::collections::HashMap;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use tera::{Context, Tera};
@@ -16,9 +16,9 @@ use tera::{Context, Tera};
 /// background = "{{ colors.base00 }}"
 /// foreground = "{{ colors.base05 }}"
 /// ```
-pub fn render_template<P: AsRef<Path>>(
+pub fn render_template<P: AsRef<Path>, C: Serialize + ?Sized>(
     template_path: P,
-    colors: &HashMap<String, String>,
+    colors: &C,
 ) -> Result<String> {
     let template_path = template_path.as_ref();
     let template_content = fs::read_to_string(template_path)
@@ -34,15 +34,46 @@ pub fn render_template<P: AsRef<Path>>(
 /// # Available filters
 /// - `hex_to_rgb`: Convert "#RRGGBB" to "0xRR,0xGG,0xBB" (for ripgrep)
 /// - `strip_hash`: Convert "#RRGGBB" to "RRGGBB"
-pub fn render_template_string(
+/// - `rgb`, `rgba(alpha=0.5)`: CSS "rgb(R,G,B)" and "rgba(R,G,B,A)"
+/// - `rgb_float`: Channels from 0.0 to 1.0, "R,G,B"
+/// - `hsl`, `hsv`: "hsl(H,S%,L%)" and "hsv(H,S%,V%)"
+/// - `hex8(alpha=0.5)`, `argb(alpha=0.5)`: "#RRGGBBAA" and "0xAARRGGBB"
+///   (`alpha` defaults to the color's own, also for `rgba`)
+/// - `xterm256`: Nearest xterm 256-color index
+/// - `ansi16`: Name of the nearest ANSI color ("red", "brightblue", ...)
+/// - `lighten(amount=0.1)`, `darken(amount=0.1)`: Shift OKLCH lightness
+/// - `saturate(amount=0.2)`, `desaturate(amount=0.2)`: Scale OKLCH chroma
+///
+/// # Available functions
+/// - `mix(a=.., b=.., t=0.5)`: Blend two colors in OKLab
+/// - `ramp(a=.., b=.., n=5)`: `n` colors from `a` to `b`
+/// - `readable_on(bg=.., candidates=[..])`: The highest-contrast candidate
+pub fn render_template_string<C: Serialize + ?Sized>(
     template_content: &str,
-    colors: &HashMap<String, String>,
+    colors: &C,
 ) -> Result<String> {
     let mut tera = Tera::default();
 
     // Register custom filters
     tera.register_filter("hex_to_rgb", filters::hex_to_rgb);
     tera.register_filter("strip_hash", filters::strip_hash);
+    tera.register_filter("rgb", filters::rgb);
+    tera.register_filter("rgba", filters::rgba);
+    tera.register_filter("rgb_float", filters::rgb_float);
+    tera.register_filter("hsl", filters::hsl);
+    tera.register_filter("hsv", filters::hsv);
+    tera.register_filter("hex8", filters::hex8);
+    tera.register_filter("argb", filters::argb);
+    tera.register_filter("xterm256", filters::xterm256);
+    tera.register_filter("ansi16", filters::ansi16);
+    tera.register_filter("lighten", filters::lighten);
+    tera.register_filter("darken", filters::darken);
+    tera.register_filter("saturate", filters::saturate);
+    tera.register_filter("desaturate", filters::desaturate);
+
+    tera.register_function("mix", functions::mix);
+    tera.register_function("ramp", functions::ramp);
+    tera.register_function("readable_on", functions::readable_on);
 
     tera.add_raw_template("template", template_content)
         .map_err(VogixError::Template)?;
@@ -290,3 +290,62 @@ fn test_hex_to_rgb_filter_lowercase() {
 
     assert_eq!(result, "0xab,0xcd,0xef");
 }
+
+#[test]
+fn test_color_format_filters() {
+    let mut colors = HashMap::new();
+    colors.insert("red".to_string(), "#FF5733".to_string());
+
+    let template = "{{ colors.red | rgb }} {{ colors.red | rgba(alpha=0.25) }} \
+        {{ colors.red | hsl }} {{ colors.red | hex8(alpha=1) }} {{ colors.red | xterm256 }}";
+    let result = render_template_string(template, &colors).unwrap();
+
+    assert_eq!(
+        result,
+        "rgb(255,87,51) rgba(255,87,51,0.25) hsl(11,100%,60%) #ff5733ff 203"
+    );
+}
+
+#[test]
+fn test_translucent_theme_color_keeps_alpha() {
+    let mut colors = HashMap::new();
+    colors.insert(
+        "selection".to_string(),
+        crate::color::Color::parse("#ff573380").unwrap(),
+    );
+
+    let template = "{{ colors.selection }} {{ colors.selection | hex_to_rgb }} \
+        {{ colors.selection | strip_hash }} {{ colors.selection | rgba }} \
+        {{ colors.selection | argb }} {{ colors.selection | hex8(alpha=1) }} \
+        {{ colors.selection | darken(amount=0) }}";
+    let result = render_template_string(template, &colors).unwrap();
+
+    assert_eq!(
+        result,
+        "#ff573380 0xff,0x57,0x33 ff573380 rgba(255,87,51,0.502) 0x80ff5733 #ff5733ff #ff573380"
+    );
+}
+
+#[test]
+fn test_color_format_filter_invalid_alpha() {
+    let mut colors = HashMap::new();
+    colors.insert("red".to_string(), "#FF5733".to_string());
+
+    let result = render_template_string("{{ colors.red | rgba(alpha=2) }}", &colors);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_color_math_in_template() {
+    let mut colors = HashMap::new();
+    colors.insert("bg".to_string(), "#000000".to_string());
+    colors.insert("fg".to_string(), "#ffffff".to_string());
+
+    let template = "{{ colors.bg | lighten(amount=1) }} \
+        {{ mix(a=colors.bg, b=colors.fg) }} \
+        {% for shade in ramp(a=colors.bg, b=colors.fg, n=3) %}{{ shade }},{% endfor %} \
+        {{ readable_on(bg=colors.bg, candidates=[colors.bg, colors.fg]) }}";
+    let result = render_template_string(template, &colors).unwrap();
+
+    assert_eq!(result, "#ffffff #636363 #000000,#636363,#ffffff, #ffffff");
+}
@@ -32,66 +32,93 @@
 //! ...
 //! ```
 
+use super::SchemeKeys;
+use crate::color::Color;
 use crate::errors::{Result, VogixError};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 #[derive(Deserialize)]
 struct Ansi16Theme {
-    colors: Ansi16Colors,
+    /// Color sections by name; Alacritty has more than are loaded here
+    colors: BTreeMap<String, toml::Value>,
 }
 
-#[derive(Deserialize)]
-struct Ansi16Colors {
-    primary: Option<Ansi16Primary>,
-    cursor: Option<Ansi16Cursor>,
-    selection: Option<Ansi16Selection>,
-    normal: Option<Ansi16Normal>,
-    bright: Option<Ansi16Bright>,
-}
-
-#[derive(Deserialize)]
-struct Ansi16Primary {
-    background: Option<String>,
-    foreground: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct Ansi16Cursor {
-    cursor: Option<String>,
-    text: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct Ansi16Selection {
-    background: Option<String>,
-    text: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct Ansi16Normal {
-    black: Option<String>,
-    red: Option<String>,
-    green: Option<String>,
-    yellow: Option<String>,
-    blue: Option<String>,
-    magenta: Option<String>,
-    cyan: Option<String>,
-    white: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct Ansi16Bright {
-    black: Option<String>,
-    red: Option<String>,
-    green: Option<String>,
-    yellow: Option<String>,
-    blue: Option<String>,
-    magenta: Option<String>,
-    cyan: Option<String>,
-    white: Option<String>,
-}
+/// Keys under `[colors]` and the names they are loaded as
+const NAMES: [(&str, &str); 22] = [
+    ("primary.background", "background"),
+    ("primary.foreground", "foreground"),
+    ("cursor.cursor", "cursor_bg"),
+    ("cursor.text", "cursor_fg"),
+    ("selection.background", "selection_bg"),
+    ("selection.text", "selection_fg"),
+    ("normal.black", "color00"),
+    ("normal.red", "color01"),
+    ("normal.green", "color02"),
+    ("normal.yellow", "color03"),
+    ("normal.blue", "color04"),
+    ("normal.magenta", "color05"),
+    ("normal.cyan", "color06"),
+    ("normal.white", "color07"),
+    ("bright.black", "color08"),
+    ("bright.red", "color09"),
+    ("bright.green", "color10"),
+    ("bright.yellow", "color11"),
+    ("bright.blue", "color12"),
+    ("bright.magenta", "color13"),
+    ("bright.cyan", "color14"),
+    ("bright.white", "color15"),
+];
+
+/// Primary, normal and bright colors are required. Bright colors fall back
+/// to their normal counterparts, as on terminals without a bright palette,
+/// and the primary colors to normal black and white.
+const ANSI16: SchemeKeys = SchemeKeys {
+    table: "colors",
+    required: &[
+        "primary.background",
+        "primary.foreground",
+        "normal.black",
+        "normal.red",
+        "normal.green",
+        "normal.yellow",
+        "normal.blue",
+        "normal.magenta",
+        "normal.cyan",
+        "normal.white",
+        "bright.black",
+        "bright.red",
+        "bright.green",
+        "bright.yellow",
+        "bright.blue",
+        "bright.magenta",
+        "bright.cyan",
+        "bright.white",
+    ],
+    optional: &[
+        "cursor.cursor",
+        "cursor.text",
+        "selection.background",
+        "selection.text",
+    ],
+    fallbacks: &[
+        ("primary.background", "normal.black"),
+        ("primary.foreground", "normal.white"),
+        ("bright.black", "normal.black"),
+        ("bright.red", "normal.red"),
+        ("bright.green", "normal.green"),
+        ("bright.yellow", "normal.yellow"),
+        ("bright.blue", "normal.blue"),
+        ("bright.magenta", "normal.magenta"),
+        ("bright.cyan", "normal.cyan"),
+        ("bright.white", "normal.white"),
+    ],
+};
+
+/// Sections whose every key must be a known color; other sections may hold
+/// Alacritty settings vogix doesn't use, such as `primary.dim_foreground`
+const STRICT_SECTIONS: [&str; 2] = ["normal", "bright"];
 
 /// Load colors from an ansi16 theme file
 ///
@@ -101,108 +128,50 @@ struct Ansi16Bright {
 /// - selection: selection_bg, selection_fg
 /// - normal: color00-color07
 /// - bright: color08-color15
-pub fn load(content: &str, _path: &Path) -> Result<HashMap<String, String>> {
+///
+/// `CellForeground` and `CellBackground` resolve to the primary colors.
+pub fn load(content: &str, path: &Path, fill_missing: bool) -> Result<HashMap<String, Color>> {
     let theme: Ansi16Theme = toml::from_str(content).map_err(VogixError::TomlParse)?;
 
-    let mut colors = HashMap::new();
-
-    // Primary colors
-    if let Some(primary) = &theme.colors.primary {
-        if let Some(v) = &primary.background {
-            colors.insert("background".to_string(), v.clone());
-        }
-        if let Some(v) = &primary.foreground {
-            colors.insert("foreground".to_string(), v.clone());
+    let mut values = BTreeMap::new();
+    for (section, table) in &theme.colors {
+        let Some(table) = table.as_table() else {
+            continue;
+        };
+        for (key, value) in table {
+            let key = format!("{}.{}", section, key);
+            if STRICT_SECTIONS.contains(&section.as_str())
+                || NAMES.iter().any(|(known, _)| *known == key)
+            {
+                let value = value
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| value.to_string());
+                values.insert(key, value);
+            }
         }
     }
+    ANSI16.complete(path, &mut values, fill_missing)?;
 
-    // Cursor colors
-    if let Some(cursor) = &theme.colors.cursor {
-        if let Some(v) = &cursor.cursor {
-            colors.insert("cursor_bg".to_string(), v.clone());
-        }
-        if let Some(v) = &cursor.text {
-            colors.insert("cursor_fg".to_string(), v.clone());
-        }
-    }
+    // Alacritty lets cursor and selection colors follow the cell they're
+    // drawn on; those resolve to the primary colors
+    let resolve = |value: &str| match value {
+        "CellForeground" => values["primary.foreground"].clone(),
+        "CellBackground" => values["primary.background"].clone(),
+        _ => value.to_string(),
+    };
 
-    // Selection colors
-    if let Some(selection) = &theme.colors.selection {
-        if let Some(v) = &selection.background {
-            colors.insert("selection_bg".to_string(), v.clone());
-        }
-        if let Some(v) = &selection.text {
-            colors.insert("selection_fg".to_string(), v.clone());
+    let mut colors = HashMap::new();
+    for (key, name) in NAMES {
+        if let Some(value) = values.get(key) {
+            let color = ANSI16.parse(path, key, &resolve(value))?;
+            colors.insert(name.to_string(), color);
         }
     }
 
-    // Normal colors (color00-color07)
-    if let Some(normal) = &theme.colors.normal {
-        insert_normal_colors(&mut colors, normal);
-    }
-
-    // Bright colors (color08-color15)
-    if let Some(bright) = &theme.colors.bright {
-        insert_bright_colors(&mut colors, bright);
-    }
-
     Ok(colors)
 }
 
-fn insert_normal_colors(colors: &mut HashMap<String, String>, normal: &Ansi16Normal) {
-    if let Some(v) = &normal.black {
-        colors.insert("color00".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.red {
-        colors.insert("color01".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.green {
-        colors.insert("color02".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.yellow {
-        colors.insert("color03".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.blue {
-        colors.insert("color04".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.magenta {
-        colors.insert("color05".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.cyan {
-        colors.insert("color06".to_string(), v.clone());
-    }
-    if let Some(v) = &normal.white {
-        colors.insert("color07".to_string(), v.clone());
-    }
-}
-
-fn insert_bright_colors(colors: &mut HashMap<String, String>, bright: &Ansi16Bright) {
-    if let Some(v) = &bright.black {
-        colors.insert("color08".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.red {
-        colors.insert("color09".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.green {
-        colors.insert("color10".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.yellow {
-        colors.insert("color11".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.blue {
-        colors.insert("color12".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.magenta {
-        colors.insert("color13".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.cyan {
-        colors.insert("color14".to_string(), v.clone());
-    }
-    if let Some(v) = &bright.white {
-        colors.insert("color15".to_string(), v.clone());
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,27 +211,27 @@ cyan = "#a4ffff"
 white = "#ffffff"
 "##;
 
-        let colors = load(content, Path::new("test.toml")).unwrap();
+        let colors = load(content, Path::new("test.toml"), false).unwrap();
 
         // Primary
-        assert_eq!(colors.get("background"), Some(&"#282a36".to_string()));
-        assert_eq!(colors.get("foreground"), Some(&"#f8f8f2".to_string()));
+        assert_eq!(colors["background"].to_string(), "#282a36");
+        assert_eq!(colors["foreground"].to_string(), "#f8f8f2");
 
         // Cursor
-        assert_eq!(colors.get("cursor_bg"), Some(&"#f8f8f2".to_string()));
-        assert_eq!(colors.get("cursor_fg"), Some(&"#282a36".to_string()));
+        assert_eq!(colors["cursor_bg"].to_string(), "#f8f8f2");
+        assert_eq!(colors["cursor_fg"].to_string(), "#282a36");
 
         // Selection
-        assert_eq!(colors.get("selection_bg"), Some(&"#44475a".to_string()));
-        assert_eq!(colors.get("selection_fg"), Some(&"#ffffff".to_string()));
+        assert_eq!(colors["selection_bg"].to_string(), "#44475a");
+        assert_eq!(colors["selection_fg"].to_string(), "#ffffff");
 
         // Normal colors
-        assert_eq!(colors.get("color00"), Some(&"#21222c".to_string()));
-        assert_eq!(colors.get("color07"), Some(&"#f8f8f2".to_string()));
+        assert_eq!(colors["color00"].to_string(), "#21222c");
+        assert_eq!(colors["color07"].to_string(), "#f8f8f2");
 
         // Bright colors
-        assert_eq!(colors.get("color08"), Some(&"#6272a4".to_string()));
-        assert_eq!(colors.get("color15"), Some(&"#ffffff".to_string()));
+        assert_eq!(colors["color08"].to_string(), "#6272a4");
+        assert_eq!(colors["color15"].to_string(), "#ffffff");
     }
 
     #[test]
@@ -277,20 +246,48 @@ black = "#21222c"
 red = "#ff5555"
 "##;
 
-        let colors = load(content, Path::new("test.toml")).unwrap();
+        let err = load(content, Path::new("test.toml"), true).unwrap_err();
+        let VogixError::IncompleteTheme { missing, .. } = err else {
+            panic!("expected an incomplete theme, got {}", err);
+        };
+        // Bright colors fall back to normal ones, which are missing too
+        assert_eq!(missing.len(), 12);
+        assert!(missing.contains(&"colors.normal.green".to_string()));
+        assert!(missing.contains(&"colors.bright.green".to_string()));
+        assert!(!missing.contains(&"colors.bright.red".to_string()));
+    }
+
+    #[test]
+    fn test_load_unknown_normal_color() {
+        let content = r##"[colors.primary]
+background = "#282a36"
+foreground = "#f8f8f2"
 
-        assert_eq!(colors.get("background"), Some(&"#282a36".to_string()));
-        assert_eq!(colors.get("color00"), Some(&"#21222c".to_string()));
-        assert_eq!(colors.get("color01"), Some(&"#ff5555".to_string()));
+[colors.normal]
+black = "#21222c"
+red = "#ff5555"
+green = "#50fa7b"
+yellow = "#f1fa8c"
+blue = "#bd93f9"
+magneta = "#ff79c6"
+cyan = "#8be9fd"
+white = "#f8f8f2"
+"##;
 
-        // Missing colors should not be in the map
-        assert!(colors.get("color02").is_none());
-        assert!(colors.get("cursor_bg").is_none());
+        let err = load(content, Path::new("test.toml"), true).unwrap_err();
+        let VogixError::IncompleteTheme {
+            missing, unknown, ..
+        } = err
+        else {
+            panic!("expected an incomplete theme, got {}", err);
+        };
+        assert_eq!(missing, ["colors.normal.magenta", "colors.bright.magenta"]);
+        assert_eq!(unknown, ["colors.normal.magneta"]);
     }
 
     #[test]
     fn test_load_invalid_toml() {
-        let result = load("not valid toml {{", Path::new("test.toml"));
+        let result = load("not valid toml {{", Path::new("test.toml"), false);
         assert!(result.is_err());
     }
 }
@@ -17,25 +17,71 @@
 //! base16 defines base00-base0F (16 colors)
 //! base24 extends this with base10-base17 (24 colors total)
 
+use super::{BASE_KEYS, SchemeKeys};
+use crate::color::Color;
 use crate::errors::{Result, VogixError};
+use crate::scheme::Scheme;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// Internal struct for parsing base16/base24 YAML files.
 /// Only `palette` is needed; serde ignores other fields in the YAML.
 #[derive(Deserialize)]
 struct Base16Theme {
-    palette: HashMap<String, String>,
+    palette: BTreeMap<String, String>,
 }
 
+const BASE16: SchemeKeys = SchemeKeys {
+    table: "palette",
+    required: BASE_KEYS.split_at(16).0,
+    optional: &[],
+    fallbacks: &[],
+};
+
+/// base24's extra colors fall back to their base16 counterparts, as the
+/// base24 spec does for base16 schemes
+const BASE24: SchemeKeys = SchemeKeys {
+    table: "palette",
+    required: &BASE_KEYS,
+    optional: &[],
+    fallbacks: &[
+        ("base10", "base00"),
+        ("base11", "base00"),
+        ("base12", "base08"),
+        ("base13", "base0A"),
+        ("base14", "base0B"),
+        ("base15", "base0C"),
+        ("base16", "base0D"),
+        ("base17", "base0E"),
+    ],
+};
+
 /// Load colors from a base16 or base24 theme file
 ///
-/// Returns the palette colors as-is (base00-base0F for base16, plus base10-base17 for base24)
-pub fn load(content: &str, _path: &Path) -> Result<HashMap<String, String>> {
-    let theme: Base16Theme = serde_yaml::from_str(content).map_err(VogixError::YamlParse)?;
+/// Returns the palette colors (base00-base0F for base16, plus base10-base17 for base24)
+pub fn load(
+    content: &str,
+    path: &Path,
+    scheme: Scheme,
+    fill_missing: bool,
+) -> Result<HashMap<String, Color>> {
+    let mut theme: Base16Theme = serde_yaml::from_str(content).map_err(VogixError::YamlParse)?;
+
+    let keys = match scheme {
+        Scheme::Base24 => &BASE24,
+        _ => &BASE16,
+    };
+    keys.complete(path, &mut theme.palette, fill_missing)?;
 
-    Ok(theme.palette)
+    theme
+        .palette
+        .into_iter()
+        .map(|(key, value)| {
+            let color = keys.parse(path, &key, &value)?;
+            Ok((key, color))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -66,10 +112,10 @@ palette:
   base0F: "#f2cdcd"
 "##;
 
-        let colors = load(content, Path::new("test.yaml")).unwrap();
+        let colors = load(content, Path::new("test.yaml"), Scheme::Base16, false).unwrap();
 
-        assert_eq!(colors.get("base00"), Some(&"#1e1e2e".to_string()));
-        assert_eq!(colors.get("base0F"), Some(&"#f2cdcd".to_string()));
+        assert_eq!(colors["base00"].to_string(), "#1e1e2e");
+        assert_eq!(colors["base0F"].to_string(), "#f2cdcd");
         assert_eq!(colors.len(), 16);
     }
 
@@ -105,32 +151,40 @@ palette:
   base17: "#cba6f7"
 "##;
 
-        let colors = load(content, Path::new("test.yaml")).unwrap();
+        let colors = load(content, Path::new("test.yaml"), Scheme::Base24, false).unwrap();
 
         // Has all base16 colors
-        assert_eq!(colors.get("base00"), Some(&"#1e1e2e".to_string()));
-        assert_eq!(colors.get("base0F"), Some(&"#f2cdcd".to_string()));
+        assert_eq!(colors["base00"].to_string(), "#1e1e2e");
+        assert_eq!(colors["base0F"].to_string(), "#f2cdcd");
 
         // Has extended base24 colors
-        assert_eq!(colors.get("base10"), Some(&"#11111b".to_string()));
-        assert_eq!(colors.get("base17"), Some(&"#cba6f7".to_string()));
+        assert_eq!(colors["base10"].to_string(), "#11111b");
+        assert_eq!(colors["base17"].to_string(), "#cba6f7");
         assert_eq!(colors.len(), 24);
     }
 
     #[test]
     fn test_load_invalid_yaml() {
-        let result = load("not: valid: yaml: {{", Path::new("test.yaml"));
+        let result = load(
+            "not: valid: yaml: {{",
+            Path::new("test.yaml"),
+            Scheme::Base16,
+            false,
+        );
         assert!(result.is_err());
     }
 
     #[test]
     fn test_load_minimal() {
-        // Only required field is palette
+        // Only required field is palette, with all 16 colors
         let content = r##"palette:
   base00: "#000000"
 "##;
 
-        let colors = load(content, Path::new("test.yaml")).unwrap();
-        assert_eq!(colors.get("base00"), Some(&"#000000".to_string()));
+        let err = load(content, Path::new("test.yaml"), Scheme::Base16, true).unwrap_err();
+        assert!(matches!(
+            err,
+            VogixError::IncompleteTheme { ref missing, .. } if missing.len() == 15
+        ));
     }
 }
@@ -4,6 +4,10 @@
 //! - vogix16: TOML files with `polarity` and `[colors]` section + semantic mappings
 //! - base16_24: YAML files with `palette` section (handles both base16 and base24)
 //! - ansi16: TOML files in Alacritty format with nested color sections
+//!
+//! Every loader checks the file against its scheme's `SchemeKeys` before
+//! parsing colors, so a theme missing a color fails on load instead of
+//! part-way through rendering.
 
 mod ansi16;
 mod base16_24;
@@ -11,26 +15,133 @@ mod base16_24;
 mod tests;
 mod vogix16;
 
+use crate::color::Color;
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
+/// base00-base17, the keys of base24; base16 and vogix16 use the first 16
+const BASE_KEYS: [&str; 24] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+    "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F", "base10", "base11",
+    "base12", "base13", "base14", "base15", "base16", "base17",
+];
+
 /// Load colors from a theme file based on the scheme type
 ///
-/// Returns a HashMap of color names to hex values (e.g., "base00" -> "#1e1e2e")
+/// Returns a HashMap of color names to parsed colors (e.g., "base00" -> `#1e1e2e`).
+/// Fails listing every missing and unknown color key, or on the first color
+/// value that can't be parsed. With `fill_missing`, missing colors that have a
+/// fallback in the scheme are filled in first.
 pub fn load_theme_colors<P: AsRef<Path>>(
     path: P,
     scheme: Scheme,
-) -> Result<HashMap<String, String>> {
+    fill_missing: bool,
+) -> Result<HashMap<String, Color>> {
     let path = path.as_ref();
     let content =
         fs::read_to_string(path).map_err(|_| VogixError::ConfigNotFound(path.to_path_buf()))?;
 
     match scheme {
-        Scheme::Vogix16 => vogix16::load(&content, path),
-        Scheme::Base16 | Scheme::Base24 => base16_24::load(&content, path),
-        Scheme::Ansi16 => ansi16::load(&content, path),
+        Scheme::Vogix16 => vogix16::load(&content, path, fill_missing),
+        Scheme::Base16 | Scheme::Base24 => base16_24::load(&content, path, scheme, fill_missing),
+        Scheme::Ansi16 => ansi16::load(&content, path, fill_missing),
+    }
+}
+
+/// Palette keys of loaded colors, in palette order
+///
+/// `base00`..`base17` for base16/base24/vogix16, `color00`..`color15` for ansi16.
+/// Named keys such as `background` or vogix16's semantic names are excluded.
+pub fn palette_keys<V>(colors: &HashMap<String, V>) -> Vec<&str> {
+    let mut keys: Vec<&str> = colors
+        .keys()
+        .map(String::as_str)
+        .filter(|key| {
+            let index = key
+                .strip_prefix("base")
+                .or_else(|| key.strip_prefix("color"));
+            index.is_some_and(|i| i.len() == 2 && i.chars().all(|c| c.is_ascii_hexdigit()))
+        })
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// Parse the color at `key` in the theme file at `path`
+fn parse_color(path: &Path, key: &str, value: &str) -> Result<Color> {
+    Color::parse(value).ok_or_else(|| VogixError::InvalidColor {
+        path: path.to_path_buf(),
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// The color keys a scheme's theme files define
+struct SchemeKeys {
+    /// Table the keys are in, for error messages
+    table: &'static str,
+    /// Keys every theme file must have
+    required: &'static [&'static str],
+    /// Keys that are used if present
+    optional: &'static [&'static str],
+    /// `(key, fallback)`: with `fill_missing`, a missing key copies its fallback
+    fallbacks: &'static [(&'static str, &'static str)],
+}
+
+impl SchemeKeys {
+    /// Fill gaps from fallbacks if asked to, then check for missing and unknown keys
+    ///
+    /// `colors` maps keys to their unparsed values.
+    fn complete(
+        &self,
+        path: &Path,
+        colors: &mut BTreeMap<String, String>,
+        fill_missing: bool,
+    ) -> Result<()> {
+        if fill_missing {
+            for (key, fallback) in self.fallbacks {
+                if !colors.contains_key(*key)
+                    && let Some(value) = colors.get(*fallback).cloned()
+                {
+                    colors.insert(key.to_string(), value);
+                }
+            }
+        }
+
+        let missing: Vec<String> = self
+            .required
+            .iter()
+            .filter(|key| !colors.contains_key(**key))
+            .map(|key| self.qualified(key))
+            .collect();
+        let unknown: Vec<String> = colors
+            .keys()
+            .filter(|key| {
+                !self.required.contains(&key.as_str()) && !self.optional.contains(&key.as_str())
+            })
+            .map(|key| self.qualified(key))
+            .collect();
+
+        if missing.is_empty() && unknown.is_empty() {
+            return Ok(());
+        }
+        Err(VogixError::IncompleteTheme {
+            path: path.to_path_buf(),
+            missing,
+            unknown,
+        })
+    }
+
+    /// Parse the color at `key`
+    fn parse(&self, path: &Path, key: &str, value: &str) -> Result<Color> {
+        parse_color(path, &self.qualified(key), value)
+    }
+
+    /// `key` with its table, as it appears in error messages
+    fn qualified(&self, key: &str) -> String {
+        format!("{}.{}", self.table, key)
     }
 }
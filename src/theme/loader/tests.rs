@@ -6,7 +6,7 @@ use tempfile::NamedTempFile;
 
 #[test]
 fn test_load_theme_file_not_found() {
-    let result = load_theme_colors("/nonexistent/path.toml", Scheme::Vogix16);
+    let result = load_theme_colors("/nonexistent/path.toml", Scheme::Vogix16, false);
     assert!(result.is_err());
 }
 
@@ -38,18 +38,18 @@ base0F = "#7a5c42"
     )
     .unwrap();
 
-    let colors = load_theme_colors(file.path(), Scheme::Vogix16).unwrap();
+    let colors = load_theme_colors(file.path(), Scheme::Vogix16, false).unwrap();
 
     // Check base colors
-    assert_eq!(colors.get("base00"), Some(&"#262626".to_string()));
-    assert_eq!(colors.get("base05"), Some(&"#a29990".to_string()));
-    assert_eq!(colors.get("base0B"), Some(&"#d7503c".to_string()));
+    assert_eq!(colors["base00"].to_string(), "#262626");
+    assert_eq!(colors["base05"].to_string(), "#a29990");
+    assert_eq!(colors["base0B"].to_string(), "#d7503c");
 
     // Check semantic mappings
-    assert_eq!(colors.get("background"), Some(&"#262626".to_string()));
-    assert_eq!(colors.get("foreground_text"), Some(&"#a29990".to_string()));
-    assert_eq!(colors.get("danger"), Some(&"#d7503c".to_string()));
-    assert_eq!(colors.get("success"), Some(&"#4d5645".to_string()));
+    assert_eq!(colors["background"].to_string(), "#262626");
+    assert_eq!(colors["foreground_text"].to_string(), "#a29990");
+    assert_eq!(colors["danger"].to_string(), "#d7503c");
+    assert_eq!(colors["success"].to_string(), "#4d5645");
 }
 
 #[test]
@@ -81,12 +81,12 @@ palette:
     )
     .unwrap();
 
-    let colors = load_theme_colors(file.path(), Scheme::Base16).unwrap();
+    let colors = load_theme_colors(file.path(), Scheme::Base16, false).unwrap();
 
-    assert_eq!(colors.get("base00"), Some(&"#1e1e2e".to_string()));
-    assert_eq!(colors.get("base05"), Some(&"#cdd6f4".to_string()));
-    assert_eq!(colors.get("base08"), Some(&"#f38ba8".to_string()));
-    assert_eq!(colors.get("base0D"), Some(&"#89b4fa".to_string()));
+    assert_eq!(colors["base00"].to_string(), "#1e1e2e");
+    assert_eq!(colors["base05"].to_string(), "#cdd6f4");
+    assert_eq!(colors["base08"].to_string(), "#f38ba8");
+    assert_eq!(colors["base0D"].to_string(), "#89b4fa");
 }
 
 #[test]
@@ -127,12 +127,12 @@ palette:
     )
     .unwrap();
 
-    let colors = load_theme_colors(file.path(), Scheme::Base24).unwrap();
+    let colors = load_theme_colors(file.path(), Scheme::Base24, false).unwrap();
 
     // Base24 has all base16 colors plus base10-base17
-    assert_eq!(colors.get("base00"), Some(&"#1e1e2e".to_string()));
-    assert_eq!(colors.get("base10"), Some(&"#11111b".to_string()));
-    assert_eq!(colors.get("base17"), Some(&"#cba6f7".to_string()));
+    assert_eq!(colors["base00"].to_string(), "#1e1e2e");
+    assert_eq!(colors["base10"].to_string(), "#11111b");
+    assert_eq!(colors["base17"].to_string(), "#cba6f7");
 }
 
 #[test]
@@ -175,30 +175,30 @@ white = "#ffffff"
     )
     .unwrap();
 
-    let colors = load_theme_colors(file.path(), Scheme::Ansi16).unwrap();
+    let colors = load_theme_colors(file.path(), Scheme::Ansi16, false).unwrap();
 
     // Primary colors
-    assert_eq!(colors.get("background"), Some(&"#282a36".to_string()));
-    assert_eq!(colors.get("foreground"), Some(&"#f8f8f2".to_string()));
+    assert_eq!(colors["background"].to_string(), "#282a36");
+    assert_eq!(colors["foreground"].to_string(), "#f8f8f2");
 
     // Cursor
-    assert_eq!(colors.get("cursor_bg"), Some(&"#f8f8f2".to_string()));
-    assert_eq!(colors.get("cursor_fg"), Some(&"#282a36".to_string()));
+    assert_eq!(colors["cursor_bg"].to_string(), "#f8f8f2");
+    assert_eq!(colors["cursor_fg"].to_string(), "#282a36");
 
     // Selection
-    assert_eq!(colors.get("selection_bg"), Some(&"#44475a".to_string()));
-    assert_eq!(colors.get("selection_fg"), Some(&"#ffffff".to_string()));
+    assert_eq!(colors["selection_bg"].to_string(), "#44475a");
+    assert_eq!(colors["selection_fg"].to_string(), "#ffffff");
 
     // Normal colors
-    assert_eq!(colors.get("color00"), Some(&"#21222c".to_string()));
-    assert_eq!(colors.get("color01"), Some(&"#ff5555".to_string()));
-    assert_eq!(colors.get("color02"), Some(&"#50fa7b".to_string()));
-    assert_eq!(colors.get("color07"), Some(&"#f8f8f2".to_string()));
+    assert_eq!(colors["color00"].to_string(), "#21222c");
+    assert_eq!(colors["color01"].to_string(), "#ff5555");
+    assert_eq!(colors["color02"].to_string(), "#50fa7b");
+    assert_eq!(colors["color07"].to_string(), "#f8f8f2");
 
     // Bright colors
-    assert_eq!(colors.get("color08"), Some(&"#6272a4".to_string()));
-    assert_eq!(colors.get("color09"), Some(&"#ff6e6e".to_string()));
-    assert_eq!(colors.get("color15"), Some(&"#ffffff".to_string()));
+    assert_eq!(colors["color08"].to_string(), "#6272a4");
+    assert_eq!(colors["color09"].to_string(), "#ff6e6e");
+    assert_eq!(colors["color15"].to_string(), "#ffffff");
 }
 
 #[test]
@@ -206,7 +206,7 @@ fn test_load_invalid_vogix16_theme() {
     let mut file = NamedTempFile::new().unwrap();
     write!(file, "this is not valid toml {{{{").unwrap();
 
-    let result = load_theme_colors(file.path(), Scheme::Vogix16);
+    let result = load_theme_colors(file.path(), Scheme::Vogix16, false);
     assert!(result.is_err());
 }
 
@@ -215,33 +215,256 @@ fn test_load_invalid_base16_theme() {
     let mut file = NamedTempFile::new().unwrap();
     write!(file, "this: is: not: valid: yaml: {{{{").unwrap();
 
-    let result = load_theme_colors(file.path(), Scheme::Base16);
+    let result = load_theme_colors(file.path(), Scheme::Base16, false);
     assert!(result.is_err());
 }
 
-#[test]
-fn test_load_ansi16_partial_colors() {
-    // Test that missing optional sections don't cause errors
+/// YAML palette with the first `count` base keys, all black
+fn palette(count: usize) -> String {
+    BASE_KEYS[..count]
+        .iter()
+        .map(|key| format!("  {}: \"#000000\"\n", key))
+        .fold("palette:\n".to_string(), |palette, line| palette + &line)
+}
+
+fn theme_file(content: &str) -> NamedTempFile {
     let mut file = NamedTempFile::new().unwrap();
-    write!(
-        file,
-        r##"[colors.primary]
+    write!(file, "{}", content).unwrap();
+    file
+}
+
+const ANSI16_PRIMARY: &str = r##"[colors.primary]
 background = "#282a36"
 foreground = "#f8f8f2"
+"##;
+
+const ANSI16_NORMAL: &str = r##"[colors.normal]
+black = "#21222c"
+red = "#ff5555"
+green = "#50fa7b"
+yellow = "#f1fa8c"
+blue = "#bd93f9"
+magenta = "#ff79c6"
+cyan = "#8be9fd"
+white = "#f8f8f2"
+"##;
+
+#[test]
+fn test_load_ansi16_reports_missing_colors() {
+    let file = theme_file(
+        r##"[colors.primary]
+background = "#282a36"
 
 [colors.normal]
 black = "#21222c"
 red = "#ff5555"
-"##
-    )
-    .unwrap();
+green = "#50fa7b"
+yellow = "#f1fa8c"
+blue = "#bd93f9"
+magenta = "#ff79c6"
+cyan = "#8be9fd"
+"##,
+    );
+
+    let err = load_theme_colors(file.path(), Scheme::Ansi16, false).unwrap_err();
+    let VogixError::IncompleteTheme {
+        missing, unknown, ..
+    } = err
+    else {
+        panic!("expected an incomplete theme, got {}", err);
+    };
+    assert_eq!(missing.len(), 10);
+    assert_eq!(
+        missing[..2],
+        ["colors.primary.foreground", "colors.normal.white"]
+    );
+    assert!(
+        missing[2..]
+            .iter()
+            .all(|key| key.starts_with("colors.bright."))
+    );
+    assert!(unknown.is_empty());
+}
+
+#[test]
+fn test_load_ansi16_fills_missing_from_normal_colors() {
+    let file = theme_file(ANSI16_NORMAL);
 
-    let colors = load_theme_colors(file.path(), Scheme::Ansi16).unwrap();
+    let colors = load_theme_colors(file.path(), Scheme::Ansi16, true).unwrap();
 
-    assert_eq!(colors.get("background"), Some(&"#282a36".to_string()));
-    assert_eq!(colors.get("color00"), Some(&"#21222c".to_string()));
-    assert_eq!(colors.get("color01"), Some(&"#ff5555".to_string()));
-    // Missing colors should not be in the map
-    assert!(colors.get("color02").is_none());
-    assert!(colors.get("cursor_bg").is_none());
+    assert_eq!(colors["background"].to_string(), "#21222c");
+    assert_eq!(colors["foreground"].to_string(), "#f8f8f2");
+    assert_eq!(colors["color09"].to_string(), "#ff5555");
+    assert_eq!(colors["color15"].to_string(), "#f8f8f2");
+    // Optional colors have no fallback
+    assert!(!colors.contains_key("cursor_bg"));
+}
+
+#[test]
+fn test_load_ansi16_ignores_unused_alacritty_settings() {
+    let file = theme_file(&format!(
+        "{}dim_foreground = \"#9a9a9a\"\n\n{}\n[colors.hints.start]\nforeground = \"#000000\"\n",
+        ANSI16_PRIMARY, ANSI16_NORMAL
+    ));
+
+    let colors = load_theme_colors(file.path(), Scheme::Ansi16, true).unwrap();
+    assert!(!colors.contains_key("dim_foreground"));
+}
+
+#[test]
+fn test_load_base16_reports_missing_and_unknown_together() {
+    let content = palette(16)
+        .replace("  base0E: \"#000000\"\n", "  bsae0E: \"#000000\"\n")
+        .replace("  base0F: \"#000000\"\n", "");
+    let file = theme_file(&content);
+
+    let err = load_theme_colors(file.path(), Scheme::Base16, false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "incomplete theme {}: missing palette.base0E, palette.base0F; unknown palette.bsae0E",
+            file.path().display()
+        )
+    );
+}
+
+#[test]
+fn test_load_base16_rejects_base24_colors() {
+    let file = theme_file(&palette(24));
+
+    let err = load_theme_colors(file.path(), Scheme::Base16, false).unwrap_err();
+    assert!(matches!(
+        err,
+        VogixError::IncompleteTheme { ref missing, ref unknown, .. }
+            if missing.is_empty() && unknown.len() == 8
+    ));
+}
+
+#[test]
+fn test_load_base24_fills_missing_from_base16() {
+    let content = palette(16).replace("  base08: \"#000000\"", "  base08: \"#f38ba8\"");
+    let file = theme_file(&content);
+
+    let err = load_theme_colors(file.path(), Scheme::Base24, false).unwrap_err();
+    assert!(matches!(
+        err,
+        VogixError::IncompleteTheme { ref missing, .. } if missing.len() == 8
+    ));
+
+    let colors = load_theme_colors(file.path(), Scheme::Base24, true).unwrap();
+    assert_eq!(colors.len(), 24);
+    assert_eq!(colors["base12"].to_string(), "#f38ba8");
+}
+
+#[test]
+fn test_load_vogix16_has_no_fallbacks() {
+    let file = theme_file("[colors]\nbase00 = \"#262626\"\n");
+
+    let err = load_theme_colors(file.path(), Scheme::Vogix16, true).unwrap_err();
+    assert!(matches!(
+        err,
+        VogixError::IncompleteTheme { ref missing, .. } if missing.len() == 15
+    ));
+}
+
+#[test]
+fn test_load_normalizes_color_formats() {
+    let content = palette(16)
+        .replace("  base00: \"#000000\"", "  base00: \"#1E1E2E\"")
+        .replace("  base01: \"#000000\"", "  base01: \"181825\"")
+        .replace("  base02: \"#000000\"", "  base02: \"#fff\"")
+        .replace("  base03: \"#000000\"", "  base03: \"rgb(69, 71, 90)\"")
+        .replace("  base04: \"#000000\"", "  base04: \"#585b7080\"");
+    let file = theme_file(&content);
+
+    let colors = load_theme_colors(file.path(), Scheme::Base16, false).unwrap();
+
+    assert_eq!(colors["base00"].to_string(), "#1e1e2e");
+    assert_eq!(colors["base01"].to_string(), "#181825");
+    assert_eq!(colors["base02"].to_string(), "#ffffff");
+    assert_eq!(colors["base03"].to_string(), "#45475a");
+    assert_eq!(colors["base04"].to_string(), "#585b7080");
+}
+
+#[test]
+fn test_load_invalid_color_names_file_and_key() {
+    let content = palette(16)
+        .replace("palette:", "[colors]")
+        .replace("  base0B: \"#000000\"", "  base0B: \"#12345\"")
+        .replace(": ", " = ");
+    let file = theme_file(&content);
+
+    let err = load_theme_colors(file.path(), Scheme::Vogix16, false).unwrap_err();
+    assert!(matches!(
+        &err,
+        VogixError::InvalidColor { path, key, value }
+            if path == file.path() && key == "colors.base0B" && value == "#12345"
+    ));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "invalid color \"#12345\" for colors.base0B in {}",
+            file.path().display()
+        )
+    );
+}
+
+#[test]
+fn test_load_invalid_color_in_each_scheme() {
+    let cases = [
+        (
+            Scheme::Base24,
+            palette(24).replace("  base10: \"#000000\"", "  base10: \"nope\""),
+            "palette.base10",
+        ),
+        (
+            Scheme::Ansi16,
+            format!(
+                "{}\n{}\n[colors.bright]\nred = \"#ff6e6\"\n",
+                ANSI16_PRIMARY, ANSI16_NORMAL
+            ),
+            "colors.bright.red",
+        ),
+    ];
+    for (scheme, content, expected_key) in cases {
+        let file = theme_file(&content);
+
+        let err = load_theme_colors(file.path(), scheme, true).unwrap_err();
+        assert!(
+            matches!(&err, VogixError::InvalidColor { key, .. } if key == expected_key),
+            "{}: {}",
+            scheme,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_load_ansi16_cell_colors_resolve_to_primary() {
+    let file = theme_file(&format!(
+        "{}\n{}\n[colors.cursor]\ncursor = \"CellForeground\"\ntext = \"CellBackground\"\n",
+        ANSI16_PRIMARY, ANSI16_NORMAL
+    ));
+
+    let colors = load_theme_colors(file.path(), Scheme::Ansi16, true).unwrap();
+
+    assert_eq!(colors["cursor_bg"].to_string(), "#f8f8f2");
+    assert_eq!(colors["cursor_fg"].to_string(), "#282a36");
+}
+
+#[test]
+fn test_palette_keys_skips_named_colors() {
+    let colors: HashMap<String, String> = [
+        "base0A",
+        "base09",
+        "base10",
+        "background",
+        "foreground_text",
+        "color_bg",
+    ]
+    .into_iter()
+    .map(|k| (k.to_string(), "#000000".to_string()))
+    .collect();
+
+    assert_eq!(palette_keys(&colors), vec!["base09", "base0A", "base10"]);
 }
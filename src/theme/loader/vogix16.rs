@@ -11,18 +11,28 @@
 //! ...
 //! ```
 
+use super::{BASE_KEYS, SchemeKeys};
+use crate::color::Color;
 use crate::errors::{Result, VogixError};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// Internal struct for parsing vogix16 TOML files.
 /// Only `colors` is needed; serde ignores other fields in the TOML.
 #[derive(Deserialize)]
 struct Vogix16Theme {
-    colors: HashMap<String, String>,
+    colors: BTreeMap<String, String>,
 }
 
+/// Every vogix16 color carries a meaning, so none has a fallback
+const VOGIX16: SchemeKeys = SchemeKeys {
+    table: "colors",
+    required: BASE_KEYS.split_at(16).0,
+    optional: &[],
+    fallbacks: &[],
+};
+
 /// Semantic color mapping from base16 colors to named colors
 const SEMANTIC_MAPPINGS: &[(&str, &str)] = &[
     // Monochromatic scale
@@ -48,16 +58,24 @@ const SEMANTIC_MAPPINGS: &[(&str, &str)] = &[
 /// Load colors from a vogix16 theme file
 ///
 /// Returns base colors plus semantic mappings (e.g., "background", "foreground_text")
-pub fn load(content: &str, _path: &Path) -> Result<HashMap<String, String>> {
-    let theme: Vogix16Theme = toml::from_str(content).map_err(VogixError::TomlParse)?;
+pub fn load(content: &str, path: &Path, fill_missing: bool) -> Result<HashMap<String, Color>> {
+    let mut theme: Vogix16Theme = toml::from_str(content).map_err(VogixError::TomlParse)?;
+    VOGIX16.complete(path, &mut theme.colors, fill_missing)?;
 
     // Start with base colors
-    let mut colors = theme.colors.clone();
+    let mut colors = theme
+        .colors
+        .into_iter()
+        .map(|(key, value)| {
+            let color = VOGIX16.parse(path, &key, &value)?;
+            Ok((key, color))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
 
     // Add semantic color mappings
     for (base_color, semantic_name) in SEMANTIC_MAPPINGS {
-        if let Some(v) = theme.colors.get(*base_color) {
-            colors.insert(semantic_name.to_string(), v.clone());
+        if let Some(color) = colors.get(*base_color).copied() {
+            colors.insert(semantic_name.to_string(), color);
         }
     }
 
@@ -91,21 +109,21 @@ base0E = "#896ea4"
 base0F = "#7a5c42"
 "##;
 
-        let colors = load(content, Path::new("test.toml")).unwrap();
+        let colors = load(content, Path::new("test.toml"), false).unwrap();
 
         // Check base colors exist
-        assert_eq!(colors.get("base00"), Some(&"#262626".to_string()));
-        assert_eq!(colors.get("base0F"), Some(&"#7a5c42".to_string()));
+        assert_eq!(colors["base00"].to_string(), "#262626");
+        assert_eq!(colors["base0F"].to_string(), "#7a5c42");
 
         // Check semantic mappings
-        assert_eq!(colors.get("background"), Some(&"#262626".to_string()));
-        assert_eq!(colors.get("foreground_text"), Some(&"#a29990".to_string()));
-        assert_eq!(colors.get("danger"), Some(&"#d7503c".to_string()));
+        assert_eq!(colors["background"].to_string(), "#262626");
+        assert_eq!(colors["foreground_text"].to_string(), "#a29990");
+        assert_eq!(colors["danger"].to_string(), "#d7503c");
     }
 
     #[test]
     fn test_load_invalid_toml() {
-        let result = load("not valid toml {{", Path::new("test.toml"));
+        let result = load("not valid toml {{", Path::new("test.toml"), false);
         assert!(result.is_err());
     }
 
@@ -4,20 +4,28 @@
 //! - `ThemeInfo` and `VariantInfo` types for representing themes
 //! - Theme discovery from runtime config manifest
 //! - Theme color loading from various scheme formats
+//! - Common color roles shared by every scheme, for templates
 //! - Query functions for filtering and finding themes
-//! - Theme variant path verification
+//! - Theme variant path verification and color loading
 
 mod discovery;
 pub mod loader;
+mod palette;
 mod query;
 pub mod types;
 
+use crate::cache::theme_variant_path;
+use crate::color::Color;
+use crate::config::Config;
 use crate::errors::{Result, VogixError};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use types::ThemeInfo;
 
 // Re-export public API
-pub use discovery::discover_themes;
-pub use loader::load_theme_colors;
+pub use discovery::{discover_themes, parse_themes_from_manifest};
+pub use loader::{load_theme_colors, palette_keys};
+pub use palette::load_palette;
 pub use query::{filter_by_scheme, get_theme};
 
 /// Verify that a theme-variant directory exists in ~/.local/share/vogix/themes/
@@ -39,3 +47,16 @@ pub fn verify_theme_variant_exists(theme: &str, variant: &str) -> Result<PathBuf
 
     Ok(theme_path)
 }
+
+/// Load the color map of a variant from its theme source file
+pub fn load_variant_colors(
+    config: &Config,
+    theme_info: &ThemeInfo,
+    variant: &str,
+) -> Result<HashMap<String, Color>> {
+    let sources = config.theme_sources.as_ref().ok_or_else(|| {
+        VogixError::Config("[theme_sources] not configured in manifest".to_string())
+    })?;
+    let path = theme_variant_path(sources, &theme_info.scheme, &theme_info.name, variant);
+    load_theme_colors(&path, theme_info.scheme, sources.fill_missing)
+}
@@ -0,0 +1,237 @@
+//! Scheme-independent color roles
+//!
+//! Each scheme names its colors differently, so a template written against
+//! `base0D` only renders for base16 and base24. The palette adds a common set
+//! of roles to the loaded colors, mapped from each scheme's own keys:
+//!
+//! - `background`, `foreground`, `cursor`, `cursor_text`, `selection`,
+//!   `selection_text`, `comment`
+//! - `color00`..`color15`, the terminal ANSI colors
+//! - `danger`, `warning`, `notice`, `success`, `active`, `link`,
+//!   `highlight`, `special`
+//!
+//! The scheme's own keys stay available next to the roles. Where a scheme
+//! already has a key of the same name, the role maps to it.
+
+use super::loader::load_theme_colors;
+use crate::color::Color;
+use crate::errors::Result;
+use crate::scheme::Scheme;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `(role, keys)`: a role takes the color of the first of its keys present
+type Roles = &'static [(&'static str, &'static [&'static str])];
+
+/// base16 and base24, following the base16 styling guide
+///
+/// base24's base12-base17 give the bright ANSI colors their own shades; base16
+/// themes repeat the normal ones.
+const BASE_ROLES: Roles = &[
+    ("background", &["base00"]),
+    ("foreground", &["base05"]),
+    ("cursor", &["base05"]),
+    ("cursor_text", &["base00"]),
+    ("selection", &["base02"]),
+    ("selection_text", &["base05"]),
+    ("comment", &["base03"]),
+    ("color00", &["base00"]),
+    ("color01", &["base08"]),
+    ("color02", &["base0B"]),
+    ("color03", &["base0A"]),
+    ("color04", &["base0D"]),
+    ("color05", &["base0E"]),
+    ("color06", &["base0C"]),
+    ("color07", &["base05"]),
+    ("color08", &["base03"]),
+    ("color09", &["base12", "base08"]),
+    ("color10", &["base14", "base0B"]),
+    ("color11", &["base13", "base0A"]),
+    ("color12", &["base16", "base0D"]),
+    ("color13", &["base17", "base0E"]),
+    ("color14", &["base15", "base0C"]),
+    ("color15", &["base07"]),
+    ("danger", &["base08"]),
+    ("warning", &["base09"]),
+    ("notice", &["base0A"]),
+    ("success", &["base0B"]),
+    ("active", &["base0C"]),
+    ("link", &["base0D"]),
+    ("highlight", &["base0E"]),
+    ("special", &["base0F"]),
+];
+
+/// vogix16 keeps its own semantic slots: base08 is success and base0B danger
+const VOGIX16_ROLES: Roles = &[
+    ("background", &["base00"]),
+    ("foreground", &["base05"]),
+    ("cursor", &["base0C"]),
+    ("cursor_text", &["base00"]),
+    ("selection", &["base02"]),
+    ("selection_text", &["base05"]),
+    ("comment", &["base03"]),
+    ("color00", &["base00"]),
+    ("color01", &["base0B"]),
+    ("color02", &["base08"]),
+    ("color03", &["base09"]),
+    ("color04", &["base0D"]),
+    ("color05", &["base0E"]),
+    ("color06", &["base0C"]),
+    ("color07", &["base05"]),
+    ("color08", &["base03"]),
+    ("color09", &["base0B"]),
+    ("color10", &["base08"]),
+    ("color11", &["base09"]),
+    ("color12", &["base0D"]),
+    ("color13", &["base0E"]),
+    ("color14", &["base0C"]),
+    ("color15", &["base07"]),
+    ("danger", &["base0B"]),
+    ("warning", &["base09"]),
+    ("notice", &["base0A"]),
+    ("success", &["base08"]),
+    ("active", &["base0C"]),
+    ("link", &["base0D"]),
+    ("highlight", &["base0E"]),
+    ("special", &["base0F"]),
+];
+
+/// ansi16 has no orange or brown, so warning, notice and special use bright colors
+const ANSI16_ROLES: Roles = &[
+    ("background", &["background"]),
+    ("foreground", &["foreground"]),
+    ("cursor", &["cursor_bg", "foreground"]),
+    ("cursor_text", &["cursor_fg", "background"]),
+    ("selection", &["selection_bg", "color08"]),
+    ("selection_text", &["selection_fg", "foreground"]),
+    ("comment", &["color08"]),
+    ("color00", &["color00"]),
+    ("color01", &["color01"]),
+    ("color02", &["color02"]),
+    ("color03", &["color03"]),
+    ("color04", &["color04"]),
+    ("color05", &["color05"]),
+    ("color06", &["color06"]),
+    ("color07", &["color07"]),
+    ("color08", &["color08"]),
+    ("color09", &["color09"]),
+    ("color10", &["color10"]),
+    ("color11", &["color11"]),
+    ("color12", &["color12"]),
+    ("color13", &["color13"]),
+    ("color14", &["color14"]),
+    ("color15", &["color15"]),
+    ("danger", &["color01"]),
+    ("warning", &["color03"]),
+    ("notice", &["color11"]),
+    ("success", &["color02"]),
+    ("active", &["color06"]),
+    ("link", &["color04"]),
+    ("highlight", &["color05"]),
+    ("special", &["color13"]),
+];
+
+/// Load colors from a theme file, with the common roles added
+///
+/// Fails like [`load_theme_colors`].
+pub fn load_palette<P: AsRef<Path>>(
+    path: P,
+    scheme: Scheme,
+    fill_missing: bool,
+) -> Result<HashMap<String, Color>> {
+    let mut colors = load_theme_colors(path, scheme, fill_missing)?;
+    add_roles(&mut colors, scheme);
+    Ok(colors)
+}
+
+/// Add the common roles to colors loaded for `scheme`
+fn add_roles(colors: &mut HashMap<String, Color>, scheme: Scheme) {
+    let roles = match scheme {
+        Scheme::Vogix16 => VOGIX16_ROLES,
+        Scheme::Base16 | Scheme::Base24 => BASE_ROLES,
+        Scheme::Ansi16 => ANSI16_ROLES,
+    };
+    for (role, keys) in roles {
+        if let Some(color) = keys.iter().find_map(|key| colors.get(*key).copied()) {
+            colors.insert(role.to_string(), color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role_names(roles: Roles) -> Vec<&'static str> {
+        roles.iter().map(|(role, _)| *role).collect()
+    }
+
+    /// Keys `prefix00`.. with a distinct gray each
+    fn keys(prefix: &str, count: u8, digits: fn(u8) -> String) -> HashMap<String, Color> {
+        (0..count)
+            .map(|i| (format!("{}{}", prefix, digits(i)), Color::rgb(i, i, i)))
+            .collect()
+    }
+
+    fn base(count: u8) -> HashMap<String, Color> {
+        keys("base", count, |i| format!("{:02X}", i))
+    }
+
+    #[test]
+    fn test_every_scheme_has_the_same_roles() {
+        assert_eq!(role_names(VOGIX16_ROLES), role_names(BASE_ROLES));
+        assert_eq!(role_names(ANSI16_ROLES), role_names(BASE_ROLES));
+    }
+
+    #[test]
+    fn test_base16_roles() {
+        let mut colors = base(16);
+        add_roles(&mut colors, Scheme::Base16);
+
+        assert_eq!(colors["background"], colors["base00"]);
+        assert_eq!(colors["foreground"], colors["base05"]);
+        assert_eq!(colors["danger"], colors["base08"]);
+        // Bright colors repeat the normal ones
+        assert_eq!(colors["color09"], colors["base08"]);
+        assert_eq!(colors["color14"], colors["base0C"]);
+    }
+
+    #[test]
+    fn test_base24_bright_colors() {
+        let mut colors = base(24);
+        add_roles(&mut colors, Scheme::Base24);
+
+        assert_eq!(colors["color09"], colors["base12"]);
+        assert_eq!(colors["color12"], colors["base16"]);
+        assert_eq!(colors["color14"], colors["base15"]);
+        assert_eq!(colors["color15"], colors["base07"]);
+    }
+
+    #[test]
+    fn test_vogix16_roles_match_semantic_names() {
+        let mut colors = base(16);
+        colors.insert("danger".to_string(), colors["base0B"]);
+        colors.insert("success".to_string(), colors["base08"]);
+        add_roles(&mut colors, Scheme::Vogix16);
+
+        assert_eq!(colors["danger"], colors["base0B"]);
+        assert_eq!(colors["success"], colors["base08"]);
+        assert_eq!(colors["color01"], colors["base0B"]);
+        assert_eq!(colors["color02"], colors["base08"]);
+    }
+
+    #[test]
+    fn test_ansi16_cursor_and_selection_fall_back() {
+        let mut colors = keys("color", 16, |i| format!("{:02}", i));
+        colors.insert("background".to_string(), Color::rgb(0x28, 0x28, 0x28));
+        colors.insert("foreground".to_string(), Color::rgb(0xeb, 0xdb, 0xb2));
+        colors.insert("cursor_bg".to_string(), Color::rgb(0xfe, 0x80, 0x19));
+        add_roles(&mut colors, Scheme::Ansi16);
+
+        assert_eq!(colors["cursor"], Color::rgb(0xfe, 0x80, 0x19));
+        assert_eq!(colors["cursor_text"], colors["background"]);
+        assert_eq!(colors["selection"], colors["color08"]);
+        assert_eq!(colors["selection_text"], colors["foreground"]);
+        assert_eq!(colors["danger"], colors["color01"]);
+    }
+}
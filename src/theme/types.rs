@@ -2,9 +2,10 @@
 
 use crate::errors::{Result, VogixError};
 use crate::scheme::Scheme;
+use serde::{Deserialize, Serialize};
 
 /// Variant information with polarity and order (0 = lightest)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariantInfo {
     pub name: String,
     pub polarity: String,
@@ -12,7 +13,7 @@ pub struct VariantInfo {
 }
 
 /// Theme information from the config manifest
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeInfo {
     pub name: String,
     pub scheme: Scheme,
@@ -0,0 +1,97 @@
+//! Theme switches from separate vogix processes running at the same time
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::thread;
+use tempfile::TempDir;
+
+const MANIFEST: &str = r#"
+[default]
+theme = "gruvbox"
+variant = "dark"
+
+[themes.gruvbox]
+scheme = "base16"
+variants = ["light", "dark"]
+
+[themes.nord]
+scheme = "base16"
+variants = ["dark"]
+"#;
+
+/// A home with pre-generated gruvbox and nord variants, on gruvbox-dark
+fn setup(home: &Path) {
+    let state_dir = home.join("state/vogix");
+    fs::create_dir_all(&state_dir).unwrap();
+    fs::create_dir_all(home.join("run")).unwrap();
+    for variant in ["gruvbox-dark", "gruvbox-light", "nord-dark"] {
+        fs::create_dir_all(home.join("data/vogix/themes").join(variant)).unwrap();
+    }
+    fs::write(state_dir.join("config.toml"), MANIFEST).unwrap();
+    fs::write(
+        state_dir.join("state.toml"),
+        "current_scheme = \"base16\"\ncurrent_theme = \"gruvbox\"\ncurrent_variant = \"dark\"\n",
+    )
+    .unwrap();
+}
+
+fn vogix(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vogix"))
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_STATE_HOME", home.join("state"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        // No daemon socket here, so every switch is applied directly
+        .env("XDG_RUNTIME_DIR", home.join("run"))
+        .output()
+        .unwrap()
+}
+
+fn read_state(home: &Path) -> (String, String) {
+    let state: toml::Value =
+        toml::from_str(&fs::read_to_string(home.join("state/vogix/state.toml")).unwrap()).unwrap();
+    let field = |key: &str| state[key].as_str().unwrap().to_string();
+    (field("current_theme"), field("current_variant"))
+}
+
+#[test]
+fn test_concurrent_undo_and_theme_change() {
+    for _ in 0..8 {
+        let temp_dir = TempDir::new().unwrap();
+        let home = temp_dir.path();
+        setup(home);
+        assert!(vogix(home, &["-q", "-v", "light"]).status.success());
+
+        let undo = {
+            let home = home.to_path_buf();
+            thread::spawn(move || vogix(&home, &["-q", "undo"]))
+        };
+        let change = vogix(home, &["-q", "-t", "nord"]);
+        let undo = undo.join().unwrap();
+        assert!(undo.status.success(), "{:?}", undo);
+        assert!(change.status.success(), "{:?}", change);
+
+        // Undo then the theme change ends on nord-dark; the theme change
+        // then undo steps back to gruvbox-light
+        let (theme, variant) = read_state(home);
+        let selection = format!("{}-{}", theme, variant);
+        assert!(
+            selection == "nord-dark" || selection == "gruvbox-light",
+            "unexpected final state {}",
+            selection
+        );
+
+        // The symlink and the history cursor agree with the state
+        let link = fs::read_link(home.join("state/vogix/current-theme")).unwrap();
+        assert!(link.ends_with(&selection), "{} -> {:?}", selection, link);
+        let history: toml::Value =
+            toml::from_str(&fs::read_to_string(home.join("state/vogix/history.toml")).unwrap())
+                .unwrap();
+        let position = history["position"].as_integer().unwrap() as usize;
+        let current = &history["entries"][position];
+        assert_eq!(current["theme"].as_str(), Some(theme.as_str()));
+        assert_eq!(current["variant"].as_str(), Some(variant.as_str()));
+    }
+}
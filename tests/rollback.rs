@@ -0,0 +1,79 @@
+//! Rolling a theme switch back when a critical app fails to reload
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const MANIFEST: &str = r#"
+[default]
+theme = "gruvbox"
+variant = "dark"
+
+[themes.gruvbox]
+scheme = "base16"
+variants = ["light", "dark"]
+
+[apps.broken]
+config_path = "/nonexistent/broken.conf"
+reload_method = "command"
+reload_command = "exit 1"
+critical = true
+"#;
+
+/// Applied by auto a while ago, before the failed switch
+const STATE: &str = r#"current_scheme = "base16"
+current_theme = "gruvbox"
+current_variant = "dark"
+last_applied = "2024-01-01T00:00:00+00:00"
+
+[provenance]
+applied_by = "auto"
+"#;
+
+fn setup(home: &Path) {
+    let state_dir = home.join("state/vogix");
+    fs::create_dir_all(&state_dir).unwrap();
+    fs::create_dir_all(home.join("run")).unwrap();
+    for variant in ["gruvbox-dark", "gruvbox-light"] {
+        fs::create_dir_all(home.join("data/vogix/themes").join(variant)).unwrap();
+    }
+    fs::write(state_dir.join("config.toml"), MANIFEST).unwrap();
+    fs::write(state_dir.join("state.toml"), STATE).unwrap();
+    std::os::unix::fs::symlink(
+        home.join("data/vogix/themes/gruvbox-dark"),
+        state_dir.join("current-theme"),
+    )
+    .unwrap();
+}
+
+fn vogix(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vogix"))
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_STATE_HOME", home.join("state"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        // No daemon socket here, so the switch is applied directly
+        .env("XDG_RUNTIME_DIR", home.join("run"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_rollback_restores_state_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path();
+    setup(home);
+
+    let output = vogix(home, &["-q", "-v", "light"]);
+    assert!(!output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("rolled back to gruvbox-dark"), "{}", stderr);
+
+    // last_applied and provenance are those of the switch before, not the rollback
+    let state = fs::read_to_string(home.join("state/vogix/state.toml")).unwrap();
+    assert_eq!(state, STATE);
+    let link = fs::read_link(home.join("state/vogix/current-theme")).unwrap();
+    assert!(link.ends_with("gruvbox-dark"), "{:?}", link);
+}